@@ -0,0 +1,107 @@
+//! settings for the code generator.
+//!
+//! the parser and generator only assume the layout OpenAPI Generator's `rust`
+//! target produces (an `apis` folder of `*_api.rs` modules whose functions take
+//! a configuration and return `Result<T, Error<E>>`, and a `models` module).
+//! everything specific to one API, like the client's name or which IDs get a
+//! newtype, comes from a `GeneratorConfig`.
+//!
+//! the crate including the generated code must define the client struct with a
+//! `config` field of `config_type`, a `Raw{client_name}<'a>` struct with a
+//! `client` field, and a `BoxFuture` alias; see the `terminal` crate's `lib.rs`.
+
+/// names and conventions of the OpenAPI-generated crate a client is generated for
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratorConfig {
+    /// the crate the generated code ends up in, used in doc examples, e.g. `terminal`
+    pub crate_name: String,
+    /// the API's name in docs, also prefixing the generated trait, e.g. `Terminal`
+    /// for the `TerminalApi` trait
+    pub api_name: String,
+    /// the client struct the methods are generated on. also names the raw client
+    /// (`Raw{client_name}`) and sub-clients (e.g. `Cart{client_name}`)
+    pub client_name: String,
+    /// the configuration type held by the client, as named where the generated code is included
+    pub config_type: String,
+    /// the name of the configuration param every API function takes
+    pub config_param: String,
+    /// the path of the error wrapper in API function return types, as named where the
+    /// generated code is included. only its last segment is matched when parsing
+    pub error_type: String,
+    /// the path of the APIs module, as named where the generated code is included
+    pub module_path: String,
+    /// the suffix of API module names, dropped when naming sub-clients and features
+    pub module_suffix: String,
+    /// file stems in the APIs folder that aren't API modules
+    pub excluded_files: Vec<String>,
+    /// the ID newtype the `id` param of `{verb}_{resource}_by_id` functions takes,
+    /// by resource, e.g. `("card", "CardId")`
+    pub id_types: Vec<(String, String)>,
+    /// response types that get a more meaningful name in the client, e.g.
+    /// `("models :: DeleteAddressById200Response", "models :: Ack")`
+    pub response_type_aliases: Vec<(String, String)>,
+    /// whether to gate each API module's generated items on a cargo feature named
+    /// after the module's resource, e.g. `#[cfg(feature = "cart")]`
+    pub feature_gates: bool,
+    /// the fewest API functions the client should wrap. in strict mode, finding
+    /// fewer fails the build, e.g. when a regenerated `openapi` crate drops endpoints
+    pub min_methods: Option<usize>,
+}
+
+impl GeneratorConfig {
+    /// a config for OpenAPI Generator's defaults, with the generated code
+    /// included in the crate `crate_name`
+    pub fn new(crate_name: &str, api_name: &str) -> Self {
+        GeneratorConfig {
+            crate_name: crate_name.to_string(),
+            api_name: api_name.to_string(),
+            client_name: "Client".to_string(),
+            config_type: "Config".to_string(),
+            config_param: "configuration".to_string(),
+            error_type: "apis::Error".to_string(),
+            module_path: "apis".to_string(),
+            module_suffix: "_api".to_string(),
+            excluded_files: vec!["mod".to_string(), "configuration".to_string()],
+            id_types: Vec::new(),
+            response_type_aliases: Vec::new(),
+            feature_gates: false,
+            min_methods: None,
+        }
+    }
+
+    /// the name of the API module without its suffix, e.g. `cart` for `cart_api`
+    pub(crate) fn module_stem<'a>(&self, module: &'a str) -> &'a str {
+        module
+            .strip_suffix(self.module_suffix.as_str())
+            .unwrap_or(module)
+    }
+
+    /// the last segment of `error_type`, which is what return types are matched against
+    pub(crate) fn error_type_name(&self) -> &str {
+        self.error_type
+            .rsplit("::")
+            .next()
+            .unwrap_or(&self.error_type)
+            .trim()
+    }
+}
+
+#[cfg(test)]
+impl GeneratorConfig {
+    /// part of the config the `terminal` crate builds with, for tests
+    pub(crate) fn terminal() -> Self {
+        GeneratorConfig {
+            id_types: vec![
+                ("address".to_string(), "AddressId".to_string()),
+                ("card".to_string(), "CardId".to_string()),
+                ("product".to_string(), "ProductId".to_string()),
+            ],
+            response_type_aliases: vec![(
+                "models :: DeleteAddressById200Response".to_string(),
+                "models :: Ack".to_string(),
+            )],
+            feature_gates: true,
+            ..GeneratorConfig::new("terminal", "Terminal")
+        }
+    }
+}
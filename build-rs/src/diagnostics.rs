@@ -0,0 +1,206 @@
+//! diagnostics for API code the build can't generate client methods for.
+//!
+//! `parse_api_functions` only wraps functions shaped like the generator's
+//! endpoint functions. if a regenerated `openapi` crate changes that shape,
+//! or a module stops parsing, endpoints would silently disappear from the
+//! client, so every skipped public function and unparsable file is reported
+//! as a `cargo:warning`. strict mode turns those warnings into a build error,
+//! and also fails the build if fewer functions than `min_methods` are found.
+
+use std::{env, fmt};
+
+use syn::ItemFn;
+
+use crate::{BuildError, GeneratorConfig, extract_result_types};
+
+/// set to `1` or `true` to fail the build instead of dropping API functions
+pub const STRICT_MODE_ENV_VAR: &str = "TERMINAL_BUILD_STRICT";
+
+/// a public function, or a whole API module, that no client method was generated for
+#[derive(Debug, Clone, PartialEq)]
+pub struct Skipped {
+    /// the API module, e.g. `cart_api`
+    pub module_name: String,
+    /// the skipped function, or `None` if the module couldn't be parsed at all
+    pub function_name: Option<String>,
+    /// why it was skipped
+    pub reason: String,
+}
+
+impl fmt::Display for Skipped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.function_name {
+            Some(function_name) => write!(
+                f,
+                "skipped `{}::{}`: {}",
+                self.module_name, function_name, self.reason
+            ),
+            None => write!(f, "skipped `{}`: {}", self.module_name, self.reason),
+        }
+    }
+}
+
+/// why a public function can't be wrapped in a client method, or `None` if it can
+pub(crate) fn skip_reason(func: &ItemFn, config: &GeneratorConfig) -> Option<String> {
+    if func.sig.asyncness.is_none() {
+        return Some("not async".to_string());
+    }
+
+    let has_config_param = func.sig.inputs.iter().any(|arg| match arg {
+        syn::FnArg::Typed(pat_type) => {
+            matches!(&*pat_type.pat, syn::Pat::Ident(pat_ident) if pat_ident.ident == config.config_param)
+        }
+        syn::FnArg::Receiver(_) => false,
+    });
+    if !has_config_param {
+        return Some(format!("has no `{}` parameter", config.config_param));
+    }
+
+    if extract_result_types(&func.sig.output, config).is_none() {
+        return Some(format!(
+            "doesn't return `Result<T, {}<E>>`",
+            config.error_type_name()
+        ));
+    }
+
+    None
+}
+
+/// whether strict mode is enabled through `TERMINAL_BUILD_STRICT`
+pub fn strict_mode() -> bool {
+    env::var(STRICT_MODE_ENV_VAR)
+        .map(|value| matches!(value.trim(), "1" | "true"))
+        .unwrap_or(false)
+}
+
+/// prints each skipped item as a `cargo:warning`. in strict mode, any
+/// skipped item fails the build, since the client would lose methods
+pub fn report_skipped(skipped: &[Skipped], strict: bool) -> Result<(), BuildError> {
+    for item in skipped {
+        println!("cargo:warning={}", item);
+    }
+
+    if strict && !skipped.is_empty() {
+        return Err(format!(
+            "{} API item(s) would be dropped from the client and {} is set:\n{}",
+            skipped.len(),
+            STRICT_MODE_ENV_VAR,
+            skipped
+                .iter()
+                .map(|item| format!("  - {}", item))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// compares the number of API functions found to `min_methods`, warning if there
+/// are fewer. in strict mode that fails the build, since the client lost methods
+pub fn check_method_count(
+    count: usize,
+    config: &GeneratorConfig,
+    strict: bool,
+) -> Result<(), BuildError> {
+    let Some(min_methods) = config.min_methods else {
+        return Ok(());
+    };
+    if count >= min_methods {
+        return Ok(());
+    }
+
+    let message = format!(
+        "found {} API function(s), but the client should have at least {}",
+        count, min_methods
+    );
+    if strict {
+        return Err(format!("{} and {} is set", message, STRICT_MODE_ENV_VAR).into());
+    }
+    println!("cargo:warning={}", message);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_skip_reason() {
+        let config = GeneratorConfig::terminal();
+        let valid_fn: ItemFn = parse_quote! {
+            pub async fn valid_api(configuration: &Configuration) -> Result<(), Error<()>> {
+                todo!()
+            }
+        };
+        assert_eq!(skip_reason(&valid_fn, &config), None);
+
+        let sync_fn: ItemFn = parse_quote! {
+            pub fn sync_api(configuration: &Configuration) -> Result<(), Error<()>> {
+                todo!()
+            }
+        };
+        assert_eq!(skip_reason(&sync_fn, &config).unwrap(), "not async");
+
+        let no_config_fn: ItemFn = parse_quote! {
+            pub async fn no_config_api() -> Result<(), Error<()>> {
+                todo!()
+            }
+        };
+        assert_eq!(
+            skip_reason(&no_config_fn, &config).unwrap(),
+            "has no `configuration` parameter"
+        );
+
+        let bad_return_fn: ItemFn = parse_quote! {
+            pub async fn bad_return_api(configuration: &Configuration) -> Result<(), String> {
+                todo!()
+            }
+        };
+        assert_eq!(
+            skip_reason(&bad_return_fn, &config).unwrap(),
+            "doesn't return `Result<T, Error<E>>`"
+        );
+    }
+
+    #[test]
+    fn test_report_skipped() {
+        let skipped = vec![Skipped {
+            module_name: "cart_api".to_string(),
+            function_name: Some("get_cart".to_string()),
+            reason: "not async".to_string(),
+        }];
+        assert_eq!(
+            skipped[0].to_string(),
+            "skipped `cart_api::get_cart`: not async"
+        );
+
+        assert!(report_skipped(&[], true).is_ok());
+        assert!(report_skipped(&skipped, false).is_ok());
+
+        let err = report_skipped(&skipped, true).unwrap_err().to_string();
+        assert!(err.contains("TERMINAL_BUILD_STRICT"));
+        assert!(err.contains("`cart_api::get_cart`"));
+    }
+
+    #[test]
+    fn test_check_method_count() {
+        let config = GeneratorConfig {
+            min_methods: Some(2),
+            ..GeneratorConfig::terminal()
+        };
+        assert!(check_method_count(2, &config, true).is_ok());
+        assert!(check_method_count(1, &config, false).is_ok());
+        assert!(check_method_count(1, &GeneratorConfig::terminal(), true).is_ok());
+
+        let err = check_method_count(1, &config, true)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "found 1 API function(s), but the client should have at least 2 and TERMINAL_BUILD_STRICT is set"
+        );
+    }
+}
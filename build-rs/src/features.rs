@@ -0,0 +1,450 @@
+//! cargo features per API group.
+//!
+//! every API module gets a feature named after the resource it serves (the
+//! same name as its sub-client accessor, e.g. `app` for `app_o_auth_api`).
+//! the generated client gates each method on its module's feature, and the
+//! `openapi` crate gates its API modules and the models only those modules
+//! need. the `openapi` crate has no build script, so its gates are written
+//! by hand in `apis/mod.rs` and `models/mod.rs`; `check_feature_gates`
+//! fails the build if they don't match what the code actually needs.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs,
+    path::Path,
+};
+
+use quote::{ToTokens, quote};
+
+use crate::{ApiFunctionInfo, BuildError, GeneratorConfig, group_by_module, module_resource};
+
+/// names each function's feature after the resource its module serves.
+/// functions get no feature if the config's `feature_gates` is off
+pub(crate) fn assign_features(functions: &mut [ApiFunctionInfo], config: &GeneratorConfig) {
+    if !config.feature_gates {
+        for func in functions.iter_mut() {
+            func.feature.clear();
+        }
+        return;
+    }
+
+    let features: HashMap<String, String> = group_by_module(functions)
+        .into_iter()
+        .map(|(module, funcs)| (module.to_string(), module_resource(module, &funcs, config)))
+        .collect();
+
+    for func in functions.iter_mut() {
+        func.feature = features[&func.module_name].clone();
+    }
+}
+
+/// the `#[cfg(feature = "...")]` attribute for a function's generated items,
+/// or nothing if the function has no feature
+pub(crate) fn feature_gate(func: &ApiFunctionInfo) -> proc_macro2::TokenStream {
+    let feature = &func.feature;
+    if feature.is_empty() {
+        return quote! {};
+    }
+    quote! { #[cfg(feature = #feature)] }
+}
+
+/// checks that `apis/mod.rs` gates each API module on its feature, and that
+/// `models/mod.rs` gates each model module on exactly the features that need
+/// it, i.e. whose API modules use it directly or through other models.
+/// models needed by every feature, or by none, are not gated
+pub fn check_feature_gates(
+    apis_folder: &Path,
+    models_folder: &Path,
+    functions: &[ApiFunctionInfo],
+) -> Result<(), BuildError> {
+    let mut problems = Vec::new();
+
+    let module_features: BTreeMap<String, String> = functions
+        .iter()
+        .map(|func| (func.module_name.clone(), func.feature.clone()))
+        .collect();
+    let expected_api_gates = module_features
+        .iter()
+        .map(|(module, feature)| (module.clone(), BTreeSet::from([feature.clone()])))
+        .collect();
+    problems.extend(compare_gates(
+        &apis_folder.join("mod.rs"),
+        &expected_api_gates,
+    )?);
+
+    let expected_model_gates = model_features(apis_folder, models_folder, &module_features)?;
+    problems.extend(compare_gates(
+        &models_folder.join("mod.rs"),
+        &expected_model_gates,
+    )?);
+
+    if problems.is_empty() {
+        return Ok(());
+    }
+
+    Err(format!(
+        "feature gates are out of date:\n  - {}",
+        problems.join("\n  - ")
+    )
+    .into())
+}
+
+/// maps every model module to the features that need it, or to no features
+/// if it's needed by all of them (or none) and shouldn't be gated
+fn model_features(
+    apis_folder: &Path,
+    models_folder: &Path,
+    module_features: &BTreeMap<String, String>,
+) -> Result<BTreeMap<String, BTreeSet<String>>, BuildError> {
+    let models_mod = fs::read_to_string(models_folder.join("mod.rs"))?;
+    let names = model_names(&models_mod)?;
+    let modules: BTreeSet<&str> = names.values().map(String::as_str).collect();
+
+    // which model modules each model module uses
+    let mut dependencies = HashMap::new();
+    for module in &modules {
+        let source = fs::read_to_string(models_folder.join(format!("{}.rs", module)))?;
+        let mut used = referenced_models(&source, &names)?;
+        used.remove(*module);
+        dependencies.insert(module.to_string(), used);
+    }
+
+    let all_features: BTreeSet<&String> = module_features.values().collect();
+    let mut needed_by: BTreeMap<String, BTreeSet<String>> = modules
+        .iter()
+        .map(|module| (module.to_string(), BTreeSet::new()))
+        .collect();
+
+    for (api_module, feature) in module_features {
+        let source = fs::read_to_string(apis_folder.join(format!("{}.rs", api_module)))?;
+        let mut pending: Vec<String> = referenced_models(&source, &names)?.into_iter().collect();
+        let mut reached = BTreeSet::new();
+        while let Some(module) = pending.pop() {
+            if reached.insert(module.clone()) {
+                pending.extend(dependencies[&module].iter().cloned());
+            }
+        }
+        for module in reached {
+            needed_by
+                .get_mut(&module)
+                .expect("reached modules are model modules")
+                .insert(feature.clone());
+        }
+    }
+
+    for features in needed_by.values_mut() {
+        if features.len() == all_features.len() {
+            features.clear();
+        }
+    }
+
+    Ok(needed_by)
+}
+
+/// maps the names a model module can be referred to by (its own name and the
+/// types `models/mod.rs` re-exports from it) to the module
+fn model_names(models_mod: &str) -> Result<HashMap<String, String>, BuildError> {
+    let file = syn::parse_file(models_mod)?;
+    let mut names = HashMap::new();
+
+    for item in &file.items {
+        match item {
+            syn::Item::Mod(item) => {
+                names.insert(item.ident.to_string(), item.ident.to_string());
+            }
+            syn::Item::Use(item) => {
+                let mut exports = Vec::new();
+                if let Some(module) = use_module(&item.tree, &mut exports) {
+                    for export in exports {
+                        names.insert(export, module.clone());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(names)
+}
+
+/// for `self::module::{A, B as C}`, returns `module` and collects `A` and `B`
+fn use_module(tree: &syn::UseTree, exports: &mut Vec<String>) -> Option<String> {
+    let syn::UseTree::Path(path) = tree else {
+        return None;
+    };
+    if path.ident != "self" {
+        return None;
+    }
+    let syn::UseTree::Path(module) = &*path.tree else {
+        return None;
+    };
+
+    fn collect(tree: &syn::UseTree, exports: &mut Vec<String>) {
+        match tree {
+            syn::UseTree::Name(name) => exports.push(name.ident.to_string()),
+            syn::UseTree::Rename(rename) => exports.push(rename.ident.to_string()),
+            syn::UseTree::Group(group) => group.items.iter().for_each(|t| collect(t, exports)),
+            _ => {}
+        }
+    }
+    collect(&module.tree, exports);
+
+    Some(module.ident.to_string())
+}
+
+/// the model modules a source file refers to, either by a type `models/mod.rs`
+/// re-exports (`Card`), by a path through the module (`card_brand::CardBrand`)
+/// or by invoking the macro a `#[macro_use]` module is named after
+/// (`open_enum!`). `#[cfg(test)]` items are skipped. this can over-approximate,
+/// which only means a model might stay enabled when it could have been gated
+fn referenced_models(
+    code: &str,
+    names: &HashMap<String, String>,
+) -> Result<BTreeSet<String>, BuildError> {
+    fn visit(
+        tokens: proc_macro2::TokenStream,
+        names: &HashMap<String, String>,
+        found: &mut BTreeSet<String>,
+    ) {
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(token) = tokens.next() {
+            match token {
+                proc_macro2::TokenTree::Group(group) => visit(group.stream(), names, found),
+                proc_macro2::TokenTree::Ident(ident) => {
+                    let ident = ident.to_string();
+                    let Some(module) = names.get(&ident) else {
+                        continue;
+                    };
+                    // module names are common words (`token`, `card`), so only
+                    // count them as part of a path
+                    let is_type = ident.starts_with(|c: char| c.is_ascii_uppercase());
+                    let is_path = matches!(
+                        tokens.peek(),
+                        Some(proc_macro2::TokenTree::Punct(punct))
+                            if punct.as_char() == ':' && punct.spacing() == proc_macro2::Spacing::Joint
+                    );
+                    let is_macro = matches!(
+                        tokens.peek(),
+                        Some(proc_macro2::TokenTree::Punct(punct)) if punct.as_char() == '!'
+                    );
+                    if is_type || is_path || is_macro {
+                        found.insert(module.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut file = syn::parse_file(code)?;
+    file.items.retain(|item| !is_test_only(item));
+    let tokens = file.into_token_stream();
+    let mut found = BTreeSet::new();
+    visit(tokens, names, &mut found);
+    Ok(found)
+}
+
+/// whether an item is `#[cfg(test)]`, e.g. a module's unit tests
+fn is_test_only(item: &syn::Item) -> bool {
+    let attrs = match item {
+        syn::Item::Mod(item) => &item.attrs,
+        syn::Item::Fn(item) => &item.attrs,
+        syn::Item::Use(item) => &item.attrs,
+        syn::Item::Impl(item) => &item.attrs,
+        _ => return false,
+    };
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("cfg")
+            && attr
+                .parse_args::<syn::Ident>()
+                .is_ok_and(|ident| ident == "test")
+    })
+}
+
+/// compares the `#[cfg(feature = ..)]` gates of the `mod` and `use self::..`
+/// items in a `mod.rs` with the expected features per module
+fn compare_gates(
+    mod_rs: &Path,
+    expected: &BTreeMap<String, BTreeSet<String>>,
+) -> Result<Vec<String>, BuildError> {
+    let file = syn::parse_file(&fs::read_to_string(mod_rs)?)?;
+    let mut problems = Vec::new();
+
+    for item in &file.items {
+        let (module, attrs) = match item {
+            syn::Item::Mod(item) => (item.ident.to_string(), &item.attrs),
+            syn::Item::Use(item) => match use_module(&item.tree, &mut Vec::new()) {
+                Some(module) => (module, &item.attrs),
+                None => continue,
+            },
+            _ => continue,
+        };
+        let Some(expected) = expected.get(&module) else {
+            continue;
+        };
+
+        let actual = cfg_features(attrs);
+        if &actual != expected {
+            problems.push(format!(
+                "`{}` in {} should be {}",
+                module,
+                mod_rs.display(),
+                describe_gate(expected)
+            ));
+        }
+    }
+
+    problems.dedup();
+    Ok(problems)
+}
+
+/// the features in a `#[cfg(feature = "a")]` or `#[cfg(any(feature = "a", ..))]` attribute
+fn cfg_features(attrs: &[syn::Attribute]) -> BTreeSet<String> {
+    let mut features = BTreeSet::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("cfg")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("feature") {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                features.insert(value.value());
+            } else if meta.path.is_ident("any") {
+                meta.parse_nested_meta(|meta| {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    features.insert(value.value());
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        });
+    }
+
+    features
+}
+
+fn describe_gate(features: &BTreeSet<String>) -> String {
+    let features: Vec<String> = features
+        .iter()
+        .map(|feature| format!("feature = \"{}\"", feature))
+        .collect();
+    match features.len() {
+        0 => "ungated".to_string(),
+        1 => format!("gated with `#[cfg({})]`", features[0]),
+        _ => format!("gated with `#[cfg(any({}))]`", features.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_check_feature_gates() -> Result<(), BuildError> {
+        let temp_dir = TempDir::new()?;
+        let apis = temp_dir.path().join("apis");
+        let models = temp_dir.path().join("models");
+        fs::create_dir_all(&apis)?;
+        fs::create_dir_all(&models)?;
+
+        fs::write(
+            apis.join("card_api.rs"),
+            "pub async fn get_card(configuration: &configuration::Configuration) -> Result<models::GetCard200Response, Error<GetCardError>> { todo!() }",
+        )?;
+        fs::write(
+            apis.join("cart_api.rs"),
+            "pub async fn get_cart(configuration: &configuration::Configuration) -> Result<models::Cart, Error<GetCartError>> { todo!() }",
+        )?;
+        fs::write(
+            models.join("get_card_200_response.rs"),
+            "pub struct GetCard200Response { pub data: Vec<models::Card> }",
+        )?;
+        fs::write(
+            models.join("card.rs"),
+            "pub struct Card { pub id: models::CardId }",
+        )?;
+        fs::write(
+            models.join("cart.rs"),
+            "pub struct Cart { pub card_id: models::CardId }",
+        )?;
+        fs::write(models.join("ids.rs"), "pub struct CardId(String);")?;
+
+        let (functions, _) = crate::parse_api_functions(
+            &apis,
+            &["card_api".to_string(), "cart_api".to_string()],
+            &GeneratorConfig::terminal(),
+        )?;
+
+        let gated_models = r#"
+            pub mod card;
+            pub use self::card::Card;
+            #[cfg(feature = "cart")]
+            pub mod cart;
+            #[cfg(feature = "cart")]
+            pub use self::cart::Cart;
+            pub mod get_card_200_response;
+            pub use self::get_card_200_response::GetCard200Response;
+            pub mod ids;
+            pub use self::ids::CardId;
+        "#;
+        fs::write(models.join("mod.rs"), gated_models)?;
+        fs::write(
+            apis.join("mod.rs"),
+            "#[cfg(feature = \"card\")] pub mod card_api;\n#[cfg(feature = \"cart\")] pub mod cart_api;",
+        )?;
+
+        let err = check_feature_gates(&apis, &models, &functions)
+            .unwrap_err()
+            .to_string();
+        // `ids` is needed by both features, everything else by only one
+        assert!(err.contains("`card` in"));
+        assert!(err.contains("should be gated with `#[cfg(feature = \"card\")]`"));
+        assert!(err.contains("`get_card_200_response` in"));
+        assert!(!err.contains("`cart` in"));
+        assert!(!err.contains("`ids` in"));
+
+        fs::write(
+            models.join("mod.rs"),
+            gated_models
+                .replace("pub mod card;", "#[cfg(feature = \"card\")] pub mod card;")
+                .replace(
+                    "pub use self::card::",
+                    "#[cfg(feature = \"card\")] pub use self::card::",
+                )
+                .replace(
+                    "pub mod get_card",
+                    "#[cfg(feature = \"card\")] pub mod get_card",
+                )
+                .replace(
+                    "pub use self::get_card",
+                    "#[cfg(feature = \"card\")] pub use self::get_card",
+                ),
+        )?;
+        check_feature_gates(&apis, &models, &functions)?;
+
+        temp_dir.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_referenced_models() -> Result<(), BuildError> {
+        let names = HashMap::from([
+            ("open_enum".to_string(), "open_enum".to_string()),
+            ("region".to_string(), "region".to_string()),
+            ("Region".to_string(), "region".to_string()),
+        ]);
+
+        let code = r#"
+            open_enum! { pub enum Brand { Visa => "visa", } }
+
+            #[cfg(test)]
+            mod tests {
+                use crate::models::Region;
+            }
+        "#;
+        assert_eq!(
+            referenced_models(code, &names)?,
+            BTreeSet::from(["open_enum".to_string()])
+        );
+        Ok(())
+    }
+}
@@ -1,11 +1,27 @@
 use quote::{ToTokens, quote};
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     fs,
     path::Path,
 };
 use syn::{FnArg, ItemFn, Pat, ReturnType, Type, parse_file, punctuated::Punctuated};
 
+mod config;
+mod diagnostics;
+mod features;
+mod mock;
+mod spec;
+
+pub use config::GeneratorConfig;
+use diagnostics::skip_reason;
+pub use diagnostics::{
+    STRICT_MODE_ENV_VAR, Skipped, check_method_count, report_skipped, strict_mode,
+};
+pub use features::check_feature_gates;
+use features::{assign_features, feature_gate};
+pub use mock::generate_mock_client;
+pub use spec::{check_spec_agreement, load_openapi_document};
+
 /// generic error type for build script operations
 pub type BuildError = Box<dyn std::error::Error>;
 
@@ -16,6 +32,10 @@ struct ParamInfo {
     name: String,
     /// the parameter type
     ty: String,
+    /// the expression passed to the wrapped API function for this parameter.
+    /// usually just the parameter name, but differs when the client method
+    /// takes a different type than the underlying API function
+    arg: String,
 }
 
 /// holds the success and error types (as strings) extracted from a `Result<T, Error<E>>`
@@ -25,6 +45,18 @@ struct ResultTypesInfo {
     value: String,
     /// the `E` in `Result<T, Error<E>>`
     error: String,
+    /// set when `T` is an envelope-only model like `{ data: ... }`,
+    /// in which case the client method returns the `data` field directly
+    envelope: Option<EnvelopeInfo>,
+}
+
+/// describes the `data` field of an envelope-only response model
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvelopeInfo {
+    /// the type returned by the client method, e.g. `models :: Cart`
+    data: String,
+    /// whether the `data` field is boxed (`Box<models::Cart>`) and needs to be moved out
+    boxed: bool,
 }
 
 /// contains all the info needed to generate a client method
@@ -35,12 +67,33 @@ pub struct ApiFunctionInfo {
     module_name: String,
     /// name of the function to call
     function_name: String,
+    /// name of the client method, which is `function_name` unless renamed by the naming map
+    method_name: String,
     /// success type (`T` in `Result<T, E>`)
     result_types: ResultTypesInfo,
     /// function parameters (excluding configuration)
     parameters: Vec<ParamInfo>,
     /// function documentation to preserve in client
     documentation: String,
+    /// the cargo feature that enables the function's client methods, e.g. `cart`
+    feature: String,
+    /// the HTTP request the function makes, if it could be found in its body
+    endpoint: Option<EndpointInfo>,
+    /// the HTTP statuses the function's error enum has a variant for, e.g. `[401, 500]`
+    error_statuses: Vec<u16>,
+}
+
+/// the HTTP method and path template of an API function's request
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndpointInfo {
+    /// e.g. `GET`
+    method: String,
+    /// e.g. `/address/{id}`
+    path: String,
+    /// the request body model, e.g. `PutCartItemRequest`
+    body: Option<String>,
+    /// whether the request sends the bearer token
+    requires_auth: bool,
 }
 
 /// discovers all API module names by scanning the source directory.
@@ -48,10 +101,13 @@ pub struct ApiFunctionInfo {
 /// without requiring manual registration of new modules.
 ///
 /// specifically excludes:
-/// - mod.rs: contains module organization/exports, not API endpoints
-/// - configuration.rs: contains shared config types used across modules
+/// - the config's `excluded_files`: by default `mod.rs`, which contains module
+///   organization/exports, and `configuration.rs`, which contains shared config types
 /// - non-rust files
-pub fn discover_api_module_names(folder_path: &Path) -> Result<Vec<String>, BuildError> {
+pub fn discover_api_module_names(
+    folder_path: &Path,
+    config: &GeneratorConfig,
+) -> Result<Vec<String>, BuildError> {
     let names = fs::read_dir(folder_path)?
         .filter_map(|entry| {
             let path = entry.ok()?.path();
@@ -64,7 +120,7 @@ pub fn discover_api_module_names(folder_path: &Path) -> Result<Vec<String>, Buil
             let file_name = path.file_stem()?.to_string_lossy().into_owned();
 
             // skip module organization and shared config files since we know they don't contain API endpoints
-            (!["mod", "configuration"].contains(&file_name.as_str())).then_some(file_name)
+            (!config.excluded_files.contains(&file_name)).then_some(file_name)
         })
         .collect();
 
@@ -74,18 +130,37 @@ pub fn discover_api_module_names(folder_path: &Path) -> Result<Vec<String>, Buil
 /// parses API functions from discovered modules and gets the metadata needed
 /// for client code generation.
 /// reads each module file, finds valid API functions, extracts type information,
-/// and returns a sorted list of fn metadata organized by module.
+/// and returns a sorted list of fn metadata organized by module, along with
+/// every public function and module that had to be skipped.
 pub fn parse_api_functions(
     folder_path: &Path,
     api_modules: &[String],
-) -> Result<Vec<ApiFunctionInfo>, BuildError> {
+    config: &GeneratorConfig,
+) -> Result<(Vec<ApiFunctionInfo>, Vec<Skipped>), BuildError> {
     let mut seen_functions = HashMap::new();
+    let mut skipped = Vec::new();
     let mut functions: Vec<ApiFunctionInfo> = api_modules
         .iter()
         .map(|module_name| {
             let module_path = folder_path.join(format!("{}.rs", module_name));
             let source = fs::read_to_string(&module_path)?;
-            parse_module_api_functions(&source, module_name, &mut seen_functions)
+            let parsed = parse_module_api_functions(
+                &source,
+                module_name,
+                config,
+                &mut seen_functions,
+                &mut skipped,
+            );
+            // a module that doesn't parse loses all its methods, which is
+            // reported rather than failing here so strict mode decides
+            Ok(parsed.unwrap_or_else(|err| {
+                skipped.push(Skipped {
+                    module_name: module_name.clone(),
+                    function_name: None,
+                    reason: format!("could not parse {}: {}", module_path.display(), err),
+                });
+                Vec::new()
+            }))
         })
         .collect::<Result<Vec<_>, BuildError>>()?
         .into_iter()
@@ -98,29 +173,53 @@ pub fn parse_api_functions(
             .cmp(&b.module_name)
             .then(a.function_name.cmp(&b.function_name))
     });
+    assign_features(&mut functions, config);
 
-    Ok(functions)
+    Ok((functions, skipped))
 }
 
 fn parse_module_api_functions(
     code: &str,
     module_name: &str,
+    config: &GeneratorConfig,
     seen_functions: &mut HashMap<String, usize>,
+    skipped: &mut Vec<Skipped>,
 ) -> Result<Vec<ApiFunctionInfo>, BuildError> {
     let file = parse_file(code)?;
+    let error_statuses = parse_error_statuses(&file.items);
     let functions = file
         .items
         .into_iter()
         .filter_map(|item| match item {
-            syn::Item::Fn(func) => parse_function(func, module_name),
+            syn::Item::Fn(func) => {
+                // private helpers are expected, but a public function is an
+                // endpoint the client would silently go without
+                let is_public = matches!(func.vis, syn::Visibility::Public(_));
+                if let Some(reason) = skip_reason(&func, config).filter(|_| is_public) {
+                    skipped.push(Skipped {
+                        module_name: module_name.to_string(),
+                        function_name: Some(func.sig.ident.to_string()),
+                        reason,
+                    });
+                    return None;
+                }
+                parse_function(func, module_name, config)
+            }
             _ => None,
         })
+        .map(|mut func| {
+            if let Some(statuses) = error_statuses.get(&func.result_types.error) {
+                func.error_statuses = statuses.clone();
+            }
+            func
+        })
         // handle possibility of duplicate function names
         .map(|mut func| {
             let fn_name = func.function_name.clone();
             let count = seen_functions.get(&fn_name).unwrap_or(&0);
             if *count > 0 {
                 func.function_name = format!("{}_{}", fn_name, count + 1);
+                func.method_name = func.function_name.clone();
             }
             seen_functions.insert(fn_name, count + 1);
             func
@@ -137,26 +236,143 @@ fn parse_module_api_functions(
 ///
 /// returns `None` if the function doesn't meet these requirements or
 /// if the return type doesn't match the expected `Result<T, Error<E>>` pattern.
-fn parse_function(func: ItemFn, module_name: &str) -> Option<ApiFunctionInfo> {
-    if !is_valid_api_function(&func) {
+fn parse_function(
+    func: ItemFn,
+    module_name: &str,
+    config: &GeneratorConfig,
+) -> Option<ApiFunctionInfo> {
+    if !is_valid_api_function(&func, config) {
         return None;
     }
 
     let function_name = func.sig.ident.to_string();
-    let parameters = extract_parameters(&func.sig.inputs);
-    let result_types = extract_result_types(&func.sig.output)?;
+    let parameters = apply_id_types(
+        &function_name,
+        extract_parameters(&func.sig.inputs, config),
+        config,
+    );
+    let mut result_types = extract_result_types(&func.sig.output, config)?;
+    result_types.value = alias_response_type(result_types.value, config);
     let documentation = parse_enum_doc_comment(&func.attrs);
+    let endpoint = find_endpoint(func.block.to_token_stream()).map(|endpoint| EndpointInfo {
+        body: find_body_model(&parameters),
+        ..endpoint
+    });
 
     Some(ApiFunctionInfo {
         module_name: module_name.to_string(),
+        method_name: function_name.clone(),
         function_name,
         result_types,
         parameters,
         documentation,
+        feature: config.module_stem(module_name).to_string(),
+        endpoint,
+        error_statuses: Vec::new(),
     })
 }
 
-fn is_valid_api_function(func: &ItemFn) -> bool {
+/// finds the HTTP method and path of the request an API function makes, and
+/// whether it sends the bearer token.
+/// generated functions build their URL with `format!("{}/cart/{id}", configuration.base_path, ..)`,
+/// pass `reqwest::Method::GET` to the request builder and read
+/// `configuration.bearer_access_token` only if the endpoint is authenticated, so we
+/// look for the first string literal starting with `{}/`, the first ident following
+/// `Method ::` and any use of `bearer_access_token`
+fn find_endpoint(body: proc_macro2::TokenStream) -> Option<EndpointInfo> {
+    #[derive(Default)]
+    struct Found {
+        method: Option<String>,
+        path: Option<String>,
+        requires_auth: bool,
+    }
+
+    fn visit(tokens: proc_macro2::TokenStream, found: &mut Found) {
+        let mut previous: Vec<String> = Vec::new();
+        for token in tokens {
+            match &token {
+                proc_macro2::TokenTree::Group(group) => visit(group.stream(), found),
+                proc_macro2::TokenTree::Ident(ident) => {
+                    if found.method.is_none() && previous == ["Method", ":", ":"] {
+                        found.method = Some(ident.to_string());
+                    }
+                    if ident == "bearer_access_token" {
+                        found.requires_auth = true;
+                    }
+                }
+                proc_macro2::TokenTree::Literal(literal) => {
+                    if found.path.is_none() {
+                        if let Ok(syn::Lit::Str(s)) =
+                            syn::parse_str::<syn::Lit>(&literal.to_string())
+                        {
+                            if let Some(template) = s.value().strip_prefix("{}") {
+                                if template.starts_with('/') {
+                                    found.path = Some(template.to_string());
+                                }
+                            }
+                        }
+                    }
+                }
+                proc_macro2::TokenTree::Punct(_) => {}
+            }
+            // `Method::GET` is `Method`, `:`, `:`, `GET`; keep the three tokens before this one
+            previous.push(token.to_string());
+            if previous.len() > 3 {
+                previous.remove(0);
+            }
+        }
+    }
+
+    let mut found = Found::default();
+    visit(body, &mut found);
+    Some(EndpointInfo {
+        method: found.method?,
+        path: found.path?,
+        body: None,
+        requires_auth: found.requires_auth,
+    })
+}
+
+/// finds the request body model of a function, e.g. `PutCartItemRequest` for
+/// a `put_cart_item_request: Option<models::PutCartItemRequest>` param
+fn find_body_model(params: &[ParamInfo]) -> Option<String> {
+    params.iter().find_map(|param| {
+        param
+            .ty
+            .strip_prefix("Option < models :: ")
+            .and_then(|ty| ty.strip_suffix(" >"))
+            .map(str::to_string)
+    })
+}
+
+/// maps each typed error enum in a module to the HTTP statuses it has a variant for,
+/// e.g. `GetCartError { Status401(..), Status500(..), UnknownValue(..) }` -> `[401, 500]`
+fn parse_error_statuses(items: &[syn::Item]) -> HashMap<String, Vec<u16>> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Enum(item) => Some(item),
+            _ => None,
+        })
+        .map(|item| {
+            let statuses = item
+                .variants
+                .iter()
+                .filter_map(|variant| {
+                    variant
+                        .ident
+                        .to_string()
+                        .strip_prefix("Status")?
+                        .parse()
+                        .ok()
+                })
+                .collect();
+            (item.ident.to_string(), statuses)
+        })
+        .collect()
+}
+
+fn is_valid_api_function(func: &ItemFn, config: &GeneratorConfig) -> bool {
     let is_public = matches!(func.vis, syn::Visibility::Public(_));
     let is_async = func.sig.asyncness.is_some();
 
@@ -167,7 +383,7 @@ fn is_valid_api_function(func: &ItemFn) -> bool {
         let Pat::Ident(pat_ident) = &*pat_type.pat else {
             return false;
         };
-        pat_ident.ident == "configuration"
+        pat_ident.ident == config.config_param
     });
 
     is_public && is_async && has_config_param
@@ -175,7 +391,10 @@ fn is_valid_api_function(func: &ItemFn) -> bool {
 
 /// extracts function parameters, filtering out the `configuration` param.
 /// config will be provided by the client instance rather than passed in each call
-fn extract_parameters(inputs: &Punctuated<FnArg, syn::token::Comma>) -> Vec<ParamInfo> {
+fn extract_parameters(
+    inputs: &Punctuated<FnArg, syn::token::Comma>,
+    config: &GeneratorConfig,
+) -> Vec<ParamInfo> {
     inputs
         .iter()
         .filter_map(|arg| {
@@ -193,12 +412,13 @@ fn extract_parameters(inputs: &Punctuated<FnArg, syn::token::Comma>) -> Vec<Para
 
             // exclude the configuration parameter bc we use config from the client
             // so we don't need to pass it in every function call
-            if param_name == "configuration" {
+            if param_name == config.config_param {
                 return None;
             }
 
             let param_type = pat_type.ty.to_token_stream().to_string();
             Some(ParamInfo {
+                arg: param_name.clone(),
                 name: param_name,
                 ty: param_type,
             })
@@ -206,6 +426,41 @@ fn extract_parameters(inputs: &Punctuated<FnArg, syn::token::Comma>) -> Vec<Para
         .collect()
 }
 
+/// replaces the `id: &str` param of `*_by_id` functions with the matching ID newtype,
+/// e.g. `get_card_by_id(id: &str)` becomes `get_card_by_id(id: &models::CardId)`,
+/// so an address ID can't be passed where a card ID belongs.
+/// the wrapped API function still receives a `&str`.
+fn apply_id_types(
+    function_name: &str,
+    mut params: Vec<ParamInfo>,
+    config: &GeneratorConfig,
+) -> Vec<ParamInfo> {
+    let Some(id_type) = id_type_for_function(function_name, config) else {
+        return params;
+    };
+
+    for param in params.iter_mut() {
+        if param.name == "id" && param.ty == "& str" {
+            param.ty = format!("& models :: {}", id_type);
+            param.arg = "id.as_str()".to_string();
+        }
+    }
+
+    params
+}
+
+/// finds the ID type for a function named like `{verb}_{resource}_by_id`
+fn id_type_for_function<'a>(function_name: &str, config: &'a GeneratorConfig) -> Option<&'a str> {
+    let (_verb, rest) = function_name.split_once('_')?;
+    let resource = rest.strip_suffix("_by_id")?;
+
+    config
+        .id_types
+        .iter()
+        .find(|(name, _)| name == resource)
+        .map(|(_, id_type)| id_type.as_str())
+}
+
 /// extracts the success and error types from a function's return type signature.
 /// this is crucial for generating properly typed client methods that maintain
 /// the same type safety as the original API functions.
@@ -215,9 +470,12 @@ fn extract_parameters(inputs: &Punctuated<FnArg, syn::token::Comma>) -> Vec<Para
 /// - value type: `Vec<String>`
 /// - error type: `CustomError`
 ///
-/// we specifically look for the `Error<E>` pattern in the error position since
-/// this is the standard error wrapper used throughout the API.
-fn extract_result_types(return_type: &ReturnType) -> Option<ResultTypesInfo> {
+/// we specifically look for the `Error<E>` pattern (or the config's `error_type`)
+/// in the error position since this is the standard error wrapper used throughout the API.
+pub(crate) fn extract_result_types(
+    return_type: &ReturnType,
+    config: &GeneratorConfig,
+) -> Option<ResultTypesInfo> {
     // we only care about functions that explicitly specify a return type
     // functions without return types (`-> ()`) are not valid API endpoints
     let ReturnType::Type(_, ty) = return_type else {
@@ -264,7 +522,7 @@ fn extract_result_types(return_type: &ReturnType) -> Option<ResultTypesInfo> {
 
     // verify the error type uses our `Error` wrapper type
     let error_segment = error_path.path.segments.last()?;
-    if error_segment.ident != "Error" {
+    if error_segment.ident != config.error_type_name() {
         return None;
     }
 
@@ -283,16 +541,290 @@ fn extract_result_types(return_type: &ReturnType) -> Option<ResultTypesInfo> {
     Some(ResultTypesInfo {
         value: return_type,
         error: error_type,
+        envelope: None,
     })
 }
 
-/// generates a module comment from the module name
-fn generate_module_comment(module: &str) -> Result<proc_macro2::TokenStream, BuildError> {
-    let comment = format!(
-        "/// {} API\n///\n",
-        module.replace("_api", "").to_uppercase()
-    );
-    Ok(comment.parse()?)
+/// loads the method naming map: a TOML file whose `[methods]` table maps API
+/// function names to the client method names to use instead, e.g.
+/// `get_address = "list_addresses"`
+pub fn load_method_names(path: &Path) -> Result<HashMap<String, String>, BuildError> {
+    parse_method_names(&fs::read_to_string(path)?)
+}
+
+fn parse_method_names(source: &str) -> Result<HashMap<String, String>, BuildError> {
+    let table: toml::Table = source.parse()?;
+    let Some(methods) = table.get("methods") else {
+        return Ok(HashMap::new());
+    };
+    let methods = methods
+        .as_table()
+        .ok_or("naming map: `methods` must be a table")?;
+
+    methods
+        .iter()
+        .map(|(function_name, method_name)| {
+            let method_name = method_name.as_str().ok_or_else(|| {
+                format!(
+                    "naming map: the new name for `{}` must be a string",
+                    function_name
+                )
+            })?;
+            syn::parse_str::<syn::Ident>(method_name).map_err(|_| {
+                format!(
+                    "naming map: `{}` is not a valid method name (for `{}`)",
+                    method_name, function_name
+                )
+            })?;
+            Ok((function_name.clone(), method_name.to_string()))
+        })
+        .collect()
+}
+
+/// renames client methods according to the naming map.
+/// fails if the map references a function that doesn't exist (e.g. after the
+/// spec drops an endpoint) or if two methods, or a method and the deprecated
+/// alias for an old name, would end up with the same name
+pub fn apply_method_names(
+    functions: &mut [ApiFunctionInfo],
+    names: &HashMap<String, String>,
+) -> Result<(), BuildError> {
+    let mut unknown: Vec<&str> = names
+        .keys()
+        .filter(|name| !functions.iter().any(|func| &func.function_name == *name))
+        .map(String::as_str)
+        .collect();
+    if !unknown.is_empty() {
+        unknown.sort();
+        return Err(format!(
+            "naming map references functions that don't exist: {}",
+            unknown.join(", ")
+        )
+        .into());
+    }
+
+    for func in functions.iter_mut() {
+        if let Some(method_name) = names.get(&func.function_name) {
+            func.method_name = method_name.clone();
+        }
+    }
+
+    // every method and every deprecated alias needs a unique name
+    let mut taken = HashMap::new();
+    for func in functions.iter() {
+        let mut names = vec![&func.method_name];
+        if func.method_name != func.function_name {
+            names.push(&func.function_name);
+        }
+        for name in names {
+            if let Some(other) = taken.insert(name, &func.function_name) {
+                return Err(format!(
+                    "naming map: `{}` and `{}` would both be called `{}`",
+                    other, func.function_name, name
+                )
+                .into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// finds response models that are nothing but a `{ data: ... }` envelope,
+/// keyed by model name (e.g. `GetCart200Response`).
+///
+/// only envelopes whose `data` type can be named from the client are returned:
+/// types from `models` (possibly wrapped in `Box`/`Vec`) and `String`.
+/// envelopes around a model-local type, like the `{ data: "ok" }` acknowledgement,
+/// are left alone since their payload isn't useful on its own.
+pub fn discover_envelope_types(
+    models_folder: &Path,
+) -> Result<HashMap<String, EnvelopeInfo>, BuildError> {
+    let mut envelopes = HashMap::new();
+
+    for entry in fs::read_dir(models_folder)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("rs") {
+            continue;
+        }
+
+        let source = fs::read_to_string(&path)?;
+        envelopes.extend(parse_envelope_types(&source)?);
+    }
+
+    Ok(envelopes)
+}
+
+fn parse_envelope_types(code: &str) -> Result<Vec<(String, EnvelopeInfo)>, BuildError> {
+    let file = parse_file(code)?;
+    let envelopes = file
+        .items
+        .into_iter()
+        .filter_map(|item| match item {
+            syn::Item::Struct(item) => parse_envelope_struct(&item),
+            _ => None,
+        })
+        .collect();
+
+    Ok(envelopes)
+}
+
+fn parse_envelope_struct(item: &syn::ItemStruct) -> Option<(String, EnvelopeInfo)> {
+    let syn::Fields::Named(fields) = &item.fields else {
+        return None;
+    };
+
+    // must have exactly one field, and it must be `data`
+    let [field] = fields.named.iter().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    if field.ident.as_ref()? != "data" {
+        return None;
+    }
+
+    let (ty, boxed) = match unwrap_box(&field.ty) {
+        Some(inner) => (inner, true),
+        None => (&field.ty, false),
+    };
+    let data = ty.to_token_stream().to_string();
+
+    let is_nameable = data.contains("models ::") || data == "String";
+    if !is_nameable {
+        return None;
+    }
+
+    Some((item.ident.to_string(), EnvelopeInfo { data, boxed }))
+}
+
+/// returns the `T` in `Box<T>`, if the type is a box
+fn unwrap_box(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Box" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+/// marks functions whose success type is an envelope-only model, so their
+/// client methods return the envelope's `data` directly
+pub fn unwrap_envelopes(
+    functions: &mut [ApiFunctionInfo],
+    envelopes: &HashMap<String, EnvelopeInfo>,
+) {
+    for func in functions.iter_mut() {
+        let model_name = func
+            .result_types
+            .value
+            .rsplit("::")
+            .next()
+            .unwrap_or_default()
+            .trim();
+        func.result_types.envelope = envelopes.get(model_name).cloned();
+    }
+}
+
+/// finds models that have at least one required (non-`Option`) field.
+///
+/// the spec marks every request body as optional, so the generated functions
+/// take `Option<Body>`, but a body whose schema has required fields can't be
+/// left out in practice. these are the bodies `ergonomic_params` makes required
+pub fn discover_required_models(models_folder: &Path) -> Result<HashSet<String>, BuildError> {
+    let mut required = HashSet::new();
+
+    for entry in fs::read_dir(models_folder)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("rs") {
+            continue;
+        }
+
+        let source = fs::read_to_string(&path)?;
+        required.extend(parse_required_models(&source)?);
+    }
+
+    Ok(required)
+}
+
+fn parse_required_models(code: &str) -> Result<Vec<String>, BuildError> {
+    let file = parse_file(code)?;
+    let models = file
+        .items
+        .into_iter()
+        .filter_map(|item| match item {
+            syn::Item::Struct(item) => Some(item),
+            _ => None,
+        })
+        .filter(|item| {
+            item.fields.iter().any(|field| {
+                let ty = field.ty.to_token_stream().to_string();
+                !ty.starts_with("Option <")
+            })
+        })
+        .map(|item| item.ident.to_string())
+        .collect();
+
+    Ok(models)
+}
+
+/// rewrites client method parameters into types that are easier to call with:
+/// - `&str` takes `impl AsRef<str>`, so `String`, `&String` and `&str` all work
+/// - ID params (`&models::CardId`) take `impl AsRef<models::CardId>`, so owned
+///   and borrowed IDs work but an ID of another resource still doesn't
+/// - `Option<models::Body>` takes a required `impl Into<models::Body>` when the
+///   body model has required fields (see `discover_required_models`)
+///
+/// the wrapped API function still receives the original type
+pub fn ergonomic_params(functions: &mut [ApiFunctionInfo], required_models: &HashSet<String>) {
+    for param in functions
+        .iter_mut()
+        .flat_map(|func| func.parameters.iter_mut())
+    {
+        if param.ty == "& str" {
+            param.ty = "impl AsRef < str >".to_string();
+            param.arg = format!("{}.as_ref()", param.name);
+        } else if let Some(id_type) = param.ty.strip_prefix("& models :: ") {
+            param.ty = format!("impl AsRef < models :: {} >", id_type);
+            param.arg = format!("{}.as_ref().as_str()", param.name);
+        } else if let Some(body) = param
+            .ty
+            .strip_prefix("Option < models :: ")
+            .and_then(|ty| ty.strip_suffix(" >"))
+        {
+            if required_models.contains(body) {
+                param.ty = format!("impl Into < models :: {} >", body);
+                param.arg = format!("Some({}.into())", param.name);
+            }
+        }
+    }
+}
+
+/// swaps a response type for its alias from the config's `response_type_aliases`, if it has one
+fn alias_response_type(ty: String, config: &GeneratorConfig) -> String {
+    config
+        .response_type_aliases
+        .iter()
+        .find(|(original, _)| *original == ty)
+        .map(|(_, alias)| alias.to_string())
+        .unwrap_or(ty)
+}
+
+/// generates the doc comment for a module's sub-client from the module name
+fn generate_module_comment(module: &str, accessor: &str, config: &GeneratorConfig) -> String {
+    format!(
+        "{} API\n\nEndpoints from `{}`, returned by [`{}::{}`].",
+        module_heading(config.module_stem(module)),
+        module,
+        config.client_name,
+        accessor
+    )
 }
 
 /// generates parameter list for a function
@@ -317,33 +849,478 @@ fn generate_arg_list(params: &[ParamInfo]) -> Result<proc_macro2::TokenStream, B
         return Ok(quote! {});
     }
 
+    let arg_string = params
+        .iter()
+        .map(|param| param.arg.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let args: proc_macro2::TokenStream = arg_string.parse()?;
+    Ok(quote! { , #args })
+}
+
+/// generates a single API method.
+/// if the function returns an envelope-only model, the method returns the
+/// envelope's `data` instead
+fn generate_method(
+    func: &ApiFunctionInfo,
+    config: &GeneratorConfig,
+) -> Result<proc_macro2::TokenStream, BuildError> {
+    let fn_name = syn::Ident::new(&func.function_name, proc_macro2::Span::call_site());
+    let module_path = api_module_path(func, config)?;
+    let error_type = api_error_type(func, config)?;
+
+    let param_list = generate_param_list(&func.parameters)?;
+    let arg_list = generate_arg_list(&func.parameters)?;
+
+    let method_name = syn::Ident::new(&func.method_name, proc_macro2::Span::call_site());
+    let docstring =
+        generate_method_docs(func, Some(&format!("client.{}", func.method_name)), config);
+
+    let call = quote! { #module_path::#fn_name(&self.config #arg_list).await };
+    let return_type = method_return_type(func)?;
+    let body = match &func.result_types.envelope {
+        Some(envelope) => {
+            let unwrap = if envelope.boxed {
+                quote! { *response.data }
+            } else {
+                quote! { response.data }
+            };
+            quote! { #call.map(|response| #unwrap) }
+        }
+        None => call,
+    };
+
+    let alias = generate_deprecated_alias(func, &config.client_name, &return_type, config)?;
+    let gate = feature_gate(func);
+
+    Ok(quote! {
+        #[doc = #docstring]
+        #gate
+        pub async fn #method_name(&self #param_list) -> Result<#return_type, #error_type> {
+            #body
+        }
+
+        #alias
+    })
+}
+
+/// generates a deprecated method under a renamed function's original name,
+/// forwarding to the renamed method on the same type. empty if not renamed
+fn generate_deprecated_alias(
+    func: &ApiFunctionInfo,
+    self_type: &str,
+    return_type: &syn::Type,
+    config: &GeneratorConfig,
+) -> Result<proc_macro2::TokenStream, BuildError> {
+    if func.method_name == func.function_name {
+        return Ok(quote! {});
+    }
+
+    let old_name = syn::Ident::new(&func.function_name, proc_macro2::Span::call_site());
+    let new_name = syn::Ident::new(&func.method_name, proc_macro2::Span::call_site());
+    let error_type = api_error_type(func, config)?;
+    let param_list = generate_param_list(&func.parameters)?;
+    let arg_list = generate_forwarded_arg_list(&func.parameters)?;
+
+    let note = format!("renamed to `{}`", func.method_name);
+    let docstring = format!("Old name of [`{}::{}`]", self_type, func.method_name);
+    let gate = feature_gate(func);
+
+    Ok(quote! {
+        #[doc = #docstring]
+        #[deprecated(note = #note)]
+        #gate
+        pub async fn #old_name(&self #param_list) -> Result<#return_type, #error_type> {
+            self.#new_name(#arg_list).await
+        }
+    })
+}
+
+/// builds the rustdoc for a client method: the endpoint's own documentation,
+/// followed by sections for the HTTP request, the error statuses and, if
+/// `example_call` is given (e.g. `client.cart().get`), an example
+fn generate_method_docs(
+    func: &ApiFunctionInfo,
+    example_call: Option<&str>,
+    config: &GeneratorConfig,
+) -> String {
+    let mut docs = func.documentation.clone();
+
+    if let Some(endpoint) = &func.endpoint {
+        docs.push_str(&format!(
+            "\n\n# HTTP\n\n`{} {}`",
+            endpoint.method, endpoint.path
+        ));
+    }
+
+    if !func.error_statuses.is_empty() {
+        docs.push_str(&format!(
+            "\n\n# Errors\n\nReturns `{}::ResponseError` with the body parsed into `{}` when the server responds with:\n",
+            config.error_type_name(),
+            func.result_types.error.replace(' ', "")
+        ));
+        for status in &func.error_statuses {
+            match status_reason(*status) {
+                Some(reason) => docs.push_str(&format!("\n- `{}` {}", status, reason)),
+                None => docs.push_str(&format!("\n- `{}`", status)),
+            }
+        }
+    }
+
+    if let Some(call) = example_call {
+        docs.push_str("\n\n# Example\n\n```no_run\n");
+        docs.push_str(&format!(
+            "# async fn example(client: &{}::{}) -> Result<(), Box<dyn std::error::Error>> {{\n",
+            config.crate_name, config.client_name
+        ));
+        docs.push_str(&format!("# use {}::models;\n", config.crate_name));
+        let mut args = Vec::new();
+        for param in &func.parameters {
+            let (value, arg) =
+                example_value(&param.name, &example_type(&pretty_type(&param.ty)), config);
+            docs.push_str(&format!("let {} = {};\n", param.name, value));
+            args.push(arg);
+        }
+        let args = args.join(", ");
+        docs.push_str(&format!("let response = {}({}).await?;\n", call, args));
+        docs.push_str("# Ok(())\n# }\n```");
+    }
+
+    docs
+}
+
+/// renders a type string produced by `to_token_stream` the way it would be written,
+/// e.g. `Option < models :: Cart >` -> `Option<models::Cart>`
+fn pretty_type(ty: &str) -> String {
+    ty.replace(" :: ", "::")
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace("& ", "&")
+}
+
+/// a concrete type that can be passed for a parameter of type `ty`,
+/// e.g. `impl Into<models::Cart>` -> `models::Cart`, `impl AsRef<str>` -> `&str`
+fn example_type(ty: &str) -> String {
+    if let Some(inner) = ty
+        .strip_prefix("impl Into<")
+        .and_then(|ty| ty.strip_suffix('>'))
+    {
+        inner.to_string()
+    } else if let Some(inner) = ty
+        .strip_prefix("impl AsRef<")
+        .and_then(|ty| ty.strip_suffix('>'))
+    {
+        format!("&{}", inner)
+    } else {
+        ty.to_string()
+    }
+}
+
+/// how an example builds a value of type `ty` for the param `name`, and passes
+/// it: an ID from a string, a string literal, or else the type's default, e.g.
+/// `&models::CardId` -> (`models::CardId::from("...")`, `&id`)
+fn example_value(name: &str, ty: &str, config: &GeneratorConfig) -> (String, String) {
+    let (owned, by_ref) = match ty.strip_prefix('&') {
+        Some(owned) => (owned, true),
+        None => (ty, false),
+    };
+    let type_name = owned.rsplit("::").next().unwrap_or(owned);
+
+    if owned == "str" {
+        ("\"...\"".to_string(), name.to_string())
+    } else {
+        let value = if config
+            .id_types
+            .iter()
+            .any(|(_, id_type)| id_type == type_name)
+        {
+            format!("{}::from(\"...\")", owned)
+        } else if owned.contains('<') {
+            format!("<{}>::default()", owned)
+        } else {
+            format!("{}::default()", owned)
+        };
+        let arg = if by_ref {
+            format!("&{}", name)
+        } else {
+            name.to_string()
+        };
+        (value, arg)
+    }
+}
+
+/// the reason phrase for the HTTP statuses the API documents
+fn status_reason(status: u16) -> Option<&'static str> {
+    let reason = match status {
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        409 => "Conflict",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => return None,
+    };
+    Some(reason)
+}
+
+/// the success type returned by a function's client method,
+/// which is the envelope's `data` for envelope-only responses
+fn method_return_type(func: &ApiFunctionInfo) -> Result<syn::Type, BuildError> {
+    let ty = match &func.result_types.envelope {
+        Some(envelope) => &envelope.data,
+        None => &func.result_types.value,
+    };
+    Ok(syn::parse_str(ty)?)
+}
+
+/// the path of a function's API module, e.g. `apis::cart_api`
+fn api_module_path(
+    func: &ApiFunctionInfo,
+    config: &GeneratorConfig,
+) -> Result<syn::Path, BuildError> {
+    Ok(syn::parse_str(&format!(
+        "{}::{}",
+        config.module_path, func.module_name
+    ))?)
+}
+
+/// the error type returned by a function's client method, e.g.
+/// `apis::Error<apis::cart_api::GetCartError>`
+fn api_error_type(
+    func: &ApiFunctionInfo,
+    config: &GeneratorConfig,
+) -> Result<syn::Type, BuildError> {
+    Ok(syn::parse_str(&format!(
+        "{}<{}::{}::{}>",
+        config.error_type, config.module_path, func.module_name, func.result_types.error
+    ))?)
+}
+
+/// finds the resource a module's functions operate on, e.g. `app` for the
+/// `app_o_auth_api` module whose functions are `get_app`, `post_app`, ...
+///
+/// this is the longest `_`-separated prefix shared by every function name once
+/// its HTTP verb is removed. falls back to the module name (minus the config's
+/// `module_suffix`) when the functions have nothing in common, like `miscellaneous_api`
+fn module_resource(module: &str, funcs: &[&ApiFunctionInfo], config: &GeneratorConfig) -> String {
+    let segments: Vec<Vec<&str>> = funcs
+        .iter()
+        .map(|func| {
+            let name = func.function_name.as_str();
+            let rest = name.split_once('_').map_or(name, |(_verb, rest)| rest);
+            rest.split('_').collect()
+        })
+        .collect();
+
+    let shortest = segments.iter().map(Vec::len).min().unwrap_or(0);
+    let common = (0..shortest)
+        .take_while(|&i| segments.iter().all(|s| s[i] == segments[0][i]))
+        .count();
+
+    if common == 0 {
+        return config.module_stem(module).to_string();
+    }
+
+    segments[0][..common]
+        .iter()
+        .copied()
+        .filter(|segment| *segment != "by" && *segment != "id")
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// the name of a function's method on its module's sub-client, e.g.
+/// `get_address` -> `list`, `get_address_by_id` -> `get`, `put_cart_item` -> `set_item`.
+/// functions that don't follow the `{verb}_{resource}[_...]` pattern keep their name
+fn sub_client_method_name(function_name: &str, resource: &str, has_by_id: bool) -> String {
+    let Some((verb, rest)) = function_name.split_once('_') else {
+        return function_name.to_string();
+    };
+
+    let suffix = if rest == resource {
+        ""
+    } else if let Some(suffix) = rest
+        .strip_prefix(resource)
+        .and_then(|s| s.strip_prefix('_'))
+    {
+        suffix
+    } else {
+        return function_name.to_string();
+    };
+
+    match (verb, suffix) {
+        ("get", "") if has_by_id => "list".to_string(),
+        ("get", "") | ("get", "by_id") => "get".to_string(),
+        ("delete", "") | ("delete", "by_id") => "delete".to_string(),
+        ("post", "") => "create".to_string(),
+        ("put", "") => "update".to_string(),
+        ("put", suffix) => format!("set_{}", suffix),
+        (_, "") => function_name.to_string(),
+        (_, suffix) => suffix.to_string(),
+    }
+}
+
+/// turns a snake case module stem back into the words of its tag, e.g.
+/// `app_o_auth` into "App OAuth". The generator splits every capital into its
+/// own word, so a single letter is joined onto the word after it
+fn module_heading(stem: &str) -> String {
+    let mut heading = String::new();
+    let mut joined = false;
+    for word in stem.split('_').filter(|word| !word.is_empty()) {
+        if !heading.is_empty() && !joined {
+            heading.push(' ');
+        }
+        heading.push_str(&to_pascal_case(word));
+        joined = word.len() == 1;
+    }
+    heading
+}
+
+fn to_pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// generates a namespaced sub-client for one API module: a borrowed handle
+/// returned by an accessor on `Client` (e.g. `client.cart()`), whose methods
+/// forward to the flat `Client` methods under shorter names (e.g. `.get()`)
+fn generate_sub_client(
+    module: &str,
+    funcs: &[&ApiFunctionInfo],
+    config: &GeneratorConfig,
+) -> Result<proc_macro2::TokenStream, BuildError> {
+    let resource = module_resource(module, funcs, config);
+    let has_by_id = funcs
+        .iter()
+        .any(|func| func.function_name.ends_with("_by_id"));
+
+    let accessor = syn::Ident::new(&resource, proc_macro2::Span::call_site());
+    let struct_name = syn::Ident::new(
+        &format!("{}{}", to_pascal_case(&resource), config.client_name),
+        proc_macro2::Span::call_site(),
+    );
+    let client_name = syn::Ident::new(&config.client_name, proc_macro2::Span::call_site());
+    let struct_doc = generate_module_comment(module, &resource, config);
+    let accessor_doc = format!("Endpoints from `{}`", module);
+
+    let methods = funcs
+        .iter()
+        .map(|func| {
+            let mut name = sub_client_method_name(&func.function_name, &resource, has_by_id);
+            // two functions mapping to the same short name keep their full names
+            if funcs.iter().any(|other| {
+                other.function_name != func.function_name
+                    && sub_client_method_name(&other.function_name, &resource, has_by_id) == name
+            }) {
+                name = func.function_name.clone();
+            }
+
+            let method_name = syn::Ident::new(&name, proc_macro2::Span::call_site());
+            let client_method = syn::Ident::new(&func.method_name, proc_macro2::Span::call_site());
+            let return_type = method_return_type(func)?;
+            let error_type = api_error_type(func, config)?;
+            let param_list = generate_param_list(&func.parameters)?;
+            let arg_list = generate_forwarded_arg_list(&func.parameters)?;
+            let docstring = generate_method_docs(
+                func,
+                Some(&format!("client.{}().{}", resource, name)),
+                config,
+            );
+
+            Ok(quote! {
+                #[doc = #docstring]
+                pub async fn #method_name(&self #param_list) -> Result<#return_type, #error_type> {
+                    self.client.#client_method(#arg_list).await
+                }
+            })
+        })
+        .collect::<Result<Vec<_>, BuildError>>()?;
+
+    // every function in a module has the same feature
+    let gate = feature_gate(funcs[0]);
+
+    Ok(quote! {
+        #[doc = #struct_doc]
+        #[derive(Debug, Clone, Copy)]
+        #gate
+        pub struct #struct_name<'a> {
+            client: &'a #client_name,
+        }
+
+        #gate
+        impl #client_name {
+            #[doc = #accessor_doc]
+            pub fn #accessor(&self) -> #struct_name<'_> {
+                #struct_name { client: self }
+            }
+        }
+
+        #gate
+        impl #struct_name<'_> {
+            #(#methods)*
+        }
+    })
+}
+
+/// generates the argument list for forwarding a client method's own parameters
+/// to another client method, without the leading comma
+fn generate_forwarded_arg_list(
+    params: &[ParamInfo],
+) -> Result<proc_macro2::TokenStream, BuildError> {
     let arg_string = params
         .iter()
         .map(|param| param.name.as_str())
         .collect::<Vec<_>>()
         .join(", ");
 
-    let args: proc_macro2::TokenStream = arg_string.parse()?;
-    Ok(quote! { , #args })
+    Ok(arg_string.parse()?)
 }
 
-/// generates a single API method
-fn generate_method(func: &ApiFunctionInfo) -> Result<proc_macro2::TokenStream, BuildError> {
+/// generates a single API method for `RawClient`, which always returns the
+/// full response model as defined by the API
+fn generate_raw_method(
+    func: &ApiFunctionInfo,
+    config: &GeneratorConfig,
+) -> Result<proc_macro2::TokenStream, BuildError> {
     let fn_name = syn::Ident::new(&func.function_name, proc_macro2::Span::call_site());
-    let module_name = syn::Ident::new(&func.module_name, proc_macro2::Span::call_site());
+    let module_path = api_module_path(func, config)?;
     let return_type: syn::Type = syn::parse_str(&func.result_types.value)?;
-    let error_type: syn::Type = syn::parse_str(&func.result_types.error)?;
+    let error_type = api_error_type(func, config)?;
 
     let param_list = generate_param_list(&func.parameters)?;
     let arg_list = generate_arg_list(&func.parameters)?;
 
-    let docstring = &func.documentation;
+    let method_name = syn::Ident::new(&func.method_name, proc_macro2::Span::call_site());
+    let docstring = generate_method_docs(func, None, config);
+    let alias = generate_deprecated_alias(
+        func,
+        &format!("Raw{}", config.client_name),
+        &return_type,
+        config,
+    )?;
+    let gate = feature_gate(func);
 
     Ok(quote! {
         #[doc = #docstring]
-        pub async fn #fn_name(&self #param_list) -> Result<#return_type, apis::Error<apis::#module_name::#error_type>> {
-            apis::#module_name::#fn_name(&self.config #arg_list).await
+        #gate
+        pub async fn #method_name(&self #param_list) -> Result<#return_type, #error_type> {
+            #module_path::#fn_name(&self.client.config #arg_list).await
         }
+
+        #alias
     })
 }
 
@@ -355,24 +1332,15 @@ fn generate_method(func: &ApiFunctionInfo) -> Result<proc_macro2::TokenStream, B
 /// - preserves documentation
 fn generate_api_methods(
     functions: &[ApiFunctionInfo],
+    config: &GeneratorConfig,
 ) -> Result<proc_macro2::TokenStream, BuildError> {
-    // group functions by module
-    let modules: BTreeMap<&str, Vec<&ApiFunctionInfo>> =
-        functions.iter().fold(BTreeMap::new(), |mut acc, func| {
-            acc.entry(&func.module_name).or_default().push(func);
-            acc
-        });
-
     // generate methods for each module
-    let methods: Vec<proc_macro2::TokenStream> = modules.iter().try_fold(
+    let methods: Vec<proc_macro2::TokenStream> = group_by_module(functions).values().try_fold(
         Vec::new(),
-        |mut acc, (module, funcs)| -> Result<Vec<proc_macro2::TokenStream>, BuildError> {
-            let module_comment = generate_module_comment(module)?;
-            acc.push(module_comment);
-
+        |mut acc, funcs| -> Result<Vec<proc_macro2::TokenStream>, BuildError> {
             let module_methods = funcs
                 .iter()
-                .map(|func| generate_method(func))
+                .map(|func| generate_method(func, config))
                 .collect::<Result<Vec<_>, BuildError>>()?;
 
             acc.extend(module_methods);
@@ -384,6 +1352,27 @@ fn generate_api_methods(
     Ok(quote!(#(#methods)*))
 }
 
+/// generates a sub-client for every API module
+fn generate_sub_clients(
+    functions: &[ApiFunctionInfo],
+    config: &GeneratorConfig,
+) -> Result<proc_macro2::TokenStream, BuildError> {
+    let sub_clients = group_by_module(functions)
+        .iter()
+        .map(|(module, funcs)| generate_sub_client(module, funcs, config))
+        .collect::<Result<Vec<_>, BuildError>>()?;
+
+    Ok(quote!(#(#sub_clients)*))
+}
+
+/// groups functions by module, ordered by module name
+fn group_by_module(functions: &[ApiFunctionInfo]) -> BTreeMap<&str, Vec<&ApiFunctionInfo>> {
+    functions.iter().fold(BTreeMap::new(), |mut acc, func| {
+        acc.entry(&func.module_name).or_default().push(func);
+        acc
+    })
+}
+
 /// prints a formatted build information message during the build process
 ///
 /// useful for debugging the build process and seeing what's happening.
@@ -397,39 +1386,256 @@ pub fn build_print_info(msg: &str) {
 fn parse_enum_doc_comment(attrs: &[syn::Attribute]) -> String {
     attrs
         .iter()
-        .find(|attr| attr.path().is_ident("doc"))
-        .map(|attr| &attr.meta)
-        .and_then(|meta| match meta {
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
             syn::Meta::NameValue(nv) => Some(&nv.value),
             _ => None,
         })
-        .and_then(|expr| match expr {
+        .filter_map(|expr| match expr {
             syn::Expr::Lit(syn::ExprLit {
                 lit: syn::Lit::Str(s),
                 ..
             }) => Some(s.value()),
             _ => None,
         })
-        .unwrap_or_default()
+        // `/// text` becomes `#[doc = " text"]`, so drop the space after the slashes
+        .map(|line| line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
         .trim()
         .to_string()
 }
 
+/// generates the `{api_name}Api` trait (e.g. `TerminalApi`) with a method for
+/// every endpoint, and its implementation for the client.
+///
+/// to keep the trait object-safe, methods take the concrete types behind the
+/// client's `impl Into<..>`/`impl AsRef<..>` params and return a `BoxFuture`,
+/// with every borrowed param tied to the lifetime of `&self`
+fn generate_api_trait(
+    functions: &[ApiFunctionInfo],
+    config: &GeneratorConfig,
+) -> Result<proc_macro2::TokenStream, BuildError> {
+    let trait_name = format!("{}Api", config.api_name);
+    let trait_ident = syn::Ident::new(&trait_name, proc_macro2::Span::call_site());
+    let client_name = syn::Ident::new(&config.client_name, proc_macro2::Span::call_site());
+    let mut trait_methods = Vec::new();
+    let mut impl_methods = Vec::new();
+
+    for func in functions {
+        let method_name = syn::Ident::new(&func.method_name, proc_macro2::Span::call_site());
+        let docstring = generate_method_docs(func, None, config);
+        let signature = trait_method_signature(func, config)?;
+        let args = func
+            .parameters
+            .iter()
+            .map(|param| syn::Ident::new(&param.name, proc_macro2::Span::call_site()));
+
+        let gate = feature_gate(func);
+        trait_methods.push(quote! {
+            #[doc = #docstring]
+            #gate
+            #signature;
+        });
+        impl_methods.push(quote! {
+            #gate
+            #signature {
+                Box::pin(#client_name::#method_name(self #(, #args)*))
+            }
+        });
+    }
+
+    let trait_doc = generate_api_trait_docs(functions, &trait_name, config);
+
+    Ok(quote! {
+        #[doc = #trait_doc]
+        pub trait #trait_ident: Send + Sync {
+            #(#trait_methods)*
+        }
+
+        impl #trait_ident for #client_name {
+            #(#impl_methods)*
+        }
+    })
+}
+
+/// the signature of a function's method on the API trait. params take the
+/// concrete types behind the client's `impl Into<..>`/`impl AsRef<..>` params,
+/// see `trait_param_types`
+fn trait_method_signature(
+    func: &ApiFunctionInfo,
+    config: &GeneratorConfig,
+) -> Result<proc_macro2::TokenStream, BuildError> {
+    let method_name = syn::Ident::new(&func.method_name, proc_macro2::Span::call_site());
+    let return_type = method_return_type(func)?;
+    let error_type = api_error_type(func, config)?;
+
+    let params = func
+        .parameters
+        .iter()
+        .zip(trait_param_types(func))
+        .map(|(param, ty)| {
+            let name = syn::Ident::new(&param.name, proc_macro2::Span::call_site());
+            let ty: syn::Type = match ty.strip_prefix('&') {
+                Some(borrowed) => syn::parse_str(&format!("&'a {}", borrowed))?,
+                None => syn::parse_str(&ty)?,
+            };
+            Ok(quote! { #name: #ty })
+        })
+        .collect::<Result<Vec<_>, BuildError>>()?;
+
+    Ok(quote! {
+        fn #method_name<'a>(&'a self #(, #params)*) -> BoxFuture<'a, Result<#return_type, #error_type>>
+    })
+}
+
+/// the types a function's params take on the API trait, e.g. `&str` for an
+/// `impl AsRef<str>` param, without lifetimes
+fn trait_param_types(func: &ApiFunctionInfo) -> Vec<String> {
+    func.parameters
+        .iter()
+        .map(|param| example_type(&pretty_type(&param.ty)))
+        .collect()
+}
+
+/// builds the rustdoc for the API trait, with an example calling the first
+/// method that takes no params, if there is one
+fn generate_api_trait_docs(
+    functions: &[ApiFunctionInfo],
+    trait_name: &str,
+    config: &GeneratorConfig,
+) -> String {
+    let mut docs = format!(
+        "Every endpoint of the {} API, implemented by [`{}`]\n\n\
+         Depend on this instead of `{}` to swap in a fake in tests, or to wrap \
+         the client in a decorator (logging, retries, ...). The trait is object-safe, \
+         so it can be used as `&dyn {}` or `Arc<dyn {}>`: methods \
+         take concrete types instead of `impl Into<..>` and return a [`BoxFuture`].",
+        config.api_name, config.client_name, config.client_name, trait_name, trait_name
+    );
+
+    if let Some(func) = functions.iter().find(|func| func.parameters.is_empty()) {
+        docs.push_str(&format!(
+            "\n\n```no_run\n\
+             use {crate_name}::{trait_name};\n\n\
+             async fn call(api: &dyn {trait_name}) -> Result<(), Box<dyn std::error::Error>> {{\n\
+             \x20   let response = api.{method}().await?;\n\
+             \x20   Ok(())\n\
+             }}\n\
+             # fn coerce(client: &{crate_name}::{client}) {{ let _ = call(client); }}\n\
+             ```",
+            crate_name = config.crate_name,
+            trait_name = trait_name,
+            method = func.method_name,
+            client = config.client_name,
+        ));
+    }
+
+    docs
+}
+
+/// generates the `endpoints::ALL` table describing every endpoint, as a string.
+/// fails if the HTTP request of a function couldn't be found
+pub fn generate_endpoints(
+    functions: &[ApiFunctionInfo],
+    config: &GeneratorConfig,
+) -> Result<String, BuildError> {
+    let entries = functions
+        .iter()
+        .map(|func| {
+            let endpoint = func.endpoint.as_ref().ok_or_else(|| {
+                format!(
+                    "couldn't find the HTTP method and path of `{}::{}`",
+                    func.module_name, func.function_name
+                )
+            })?;
+
+            let name = &func.method_name;
+            let operation = &func.function_name;
+            let module = &func.module_name;
+            let method = syn::Ident::new(
+                &to_pascal_case(&endpoint.method.to_lowercase()),
+                proc_macro2::Span::call_site(),
+            );
+            let path = &endpoint.path;
+            let body = match &endpoint.body {
+                Some(body) => quote! { Some(#body) },
+                None => quote! { None },
+            };
+            let error_statuses = &func.error_statuses;
+            let requires_auth = endpoint.requires_auth;
+            let summary = func.documentation.lines().next().unwrap_or_default();
+
+            let gate = feature_gate(func);
+
+            Ok(quote! {
+                #gate
+                EndpointInfo {
+                    name: #name,
+                    operation: #operation,
+                    module: #module,
+                    method: HttpMethod::#method,
+                    path: #path,
+                    body: #body,
+                    error_statuses: &[#(#error_statuses),*],
+                    requires_auth: #requires_auth,
+                    summary: #summary,
+                }
+            })
+        })
+        .collect::<Result<Vec<_>, BuildError>>()?;
+
+    let table_doc = format!(
+        "Every endpoint of the {} API, ordered by module and function name",
+        config.api_name
+    );
+    let table = quote! {
+        #[doc = #table_doc]
+        pub static ALL: &[EndpointInfo] = &[#(#entries),*];
+    };
+
+    Ok(table.to_string())
+}
+
 /// generates the complete client implementation as a string.
 /// this is the final output of the build process that creates
 /// a strongly-typed client matching the API's interface.
-pub fn generate_client_impl(functions: &[ApiFunctionInfo]) -> Result<String, BuildError> {
-    let api_methods = generate_api_methods(functions)?;
+pub fn generate_client_impl(
+    functions: &[ApiFunctionInfo],
+    config: &GeneratorConfig,
+) -> Result<String, BuildError> {
+    let api_methods = generate_api_methods(functions, config)?;
+    let raw_methods = functions
+        .iter()
+        .map(|func| generate_raw_method(func, config))
+        .collect::<Result<Vec<_>, BuildError>>()?;
+    let sub_clients = generate_sub_clients(functions, config)?;
+    let api_trait = generate_api_trait(functions, config)?;
+
+    let client_name = syn::Ident::new(&config.client_name, proc_macro2::Span::call_site());
+    let raw_client_name = syn::Ident::new(
+        &format!("Raw{}", config.client_name),
+        proc_macro2::Span::call_site(),
+    );
+    let config_type: syn::Type = syn::parse_str(&config.config_type)?;
 
     let impl_block = quote! {
-        impl Client {
+        impl #client_name {
             /// Creates a new client with the given configuration
-            pub fn new(config: Config) -> Self {
-                Client { config }
+            pub fn new(config: #config_type) -> Self {
+                #client_name { config }
             }
 
             #api_methods
         }
+
+        impl #raw_client_name<'_> {
+            #(#raw_methods)*
+        }
+
+        #sub_clients
+
+        #api_trait
     };
 
     Ok(impl_block.to_string())
@@ -453,7 +1659,7 @@ mod tests {
         fs::write(temp_path.join("configuration.rs"), "")?;
         fs::write(temp_path.join("not_a_rust_file.txt"), "")?;
 
-        let mut module_names = discover_api_module_names(temp_path)?;
+        let mut module_names = discover_api_module_names(temp_path, &GeneratorConfig::terminal())?;
         module_names.sort(); // sort for deterministic comparison
 
         assert_eq!(
@@ -499,7 +1705,11 @@ mod tests {
         "#;
         fs::write(temp_path.join("test_api.rs"), api_content)?;
 
-        let functions = parse_api_functions(temp_path, &["test_api".to_string()])?;
+        let (functions, _) = parse_api_functions(
+            temp_path,
+            &["test_api".to_string()],
+            &GeneratorConfig::terminal(),
+        )?;
 
         do_test(&functions);
 
@@ -520,7 +1730,11 @@ mod tests {
         "#;
         fs::write(temp_path.join("test_api.rs"), api_content)?;
 
-        let functions = parse_api_functions(temp_path, &["test_api".to_string()])?;
+        let (functions, _) = parse_api_functions(
+            temp_path,
+            &["test_api".to_string()],
+            &GeneratorConfig::terminal(),
+        )?;
 
         do_test(&functions);
 
@@ -551,9 +1765,10 @@ mod tests {
         "#;
         fs::write(temp_path.join("test_api2.rs"), api_content2)?;
 
-        let functions = parse_api_functions(
+        let (functions, _) = parse_api_functions(
             temp_path,
             &["test_api".to_string(), "test_api2".to_string()],
+            &GeneratorConfig::terminal(),
         )?;
         assert_eq!(functions.len(), 2);
         assert_eq!(functions[0].function_name, "test_function");
@@ -573,20 +1788,49 @@ mod tests {
         // with syntax error
         fs::write(
             temp_path.join("test_api.rs"),
-            "pub async fn test_function() -> Result<(), Error<()>> { todo!() }",
+            "pub async fn test_function( -> Result<(), Error<()>> { todo!() }",
         )?;
 
-        let functions = parse_api_functions(temp_path, &["test_api".to_string()])?;
+        let (functions, skipped) = parse_api_functions(
+            temp_path,
+            &["test_api".to_string()],
+            &GeneratorConfig::terminal(),
+        )?;
         assert!(functions.is_empty());
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].function_name, None);
+        assert!(skipped[0].reason.starts_with("could not parse"));
 
-        // with valid function but an invalid return type
+        // with valid function but no configuration param
         fs::write(
             temp_path.join("test_api.rs"),
             "pub async fn test_function() -> Result<(), Error<()>> { todo!() }",
         )?;
 
-        let functions = parse_api_functions(temp_path, &["test_api".to_string()])?;
+        let (functions, skipped) = parse_api_functions(
+            temp_path,
+            &["test_api".to_string()],
+            &GeneratorConfig::terminal(),
+        )?;
+        assert!(functions.is_empty());
+        assert_eq!(
+            skipped[0].to_string(),
+            "skipped `test_api::test_function`: has no `configuration` parameter"
+        );
+
+        // private helpers aren't reported
+        fs::write(
+            temp_path.join("test_api.rs"),
+            "fn helper() -> String { todo!() }",
+        )?;
+
+        let (functions, skipped) = parse_api_functions(
+            temp_path,
+            &["test_api".to_string()],
+            &GeneratorConfig::terminal(),
+        )?;
         assert!(functions.is_empty());
+        assert!(skipped.is_empty());
 
         // clean up
         temp_dir.close()?;
@@ -594,6 +1838,38 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_removed_api_function_fails_strict_build() -> Result<(), BuildError> {
+        let temp_dir = TempDir::new()?;
+        let temp_path = temp_dir.path();
+        let config = GeneratorConfig {
+            min_methods: Some(2),
+            ..GeneratorConfig::terminal()
+        };
+        let get_cart = "pub async fn get_cart(configuration: &configuration::Configuration) -> Result<models::GetCart200Response, Error<GetCartError>> { todo!() }";
+        let clear_cart = "pub async fn clear_cart(configuration: &configuration::Configuration) -> Result<models::ClearCart200Response, Error<ClearCartError>> { todo!() }";
+
+        fs::write(
+            temp_path.join("cart_api.rs"),
+            format!("{}\n{}", get_cart, clear_cart),
+        )?;
+        let (functions, _) = parse_api_functions(temp_path, &["cart_api".to_string()], &config)?;
+        assert!(check_method_count(functions.len(), &config, true).is_ok());
+
+        // regenerating the crate dropped `clear_cart` without leaving anything to skip
+        fs::write(temp_path.join("cart_api.rs"), get_cart)?;
+        let (functions, skipped) =
+            parse_api_functions(temp_path, &["cart_api".to_string()], &config)?;
+        assert!(skipped.is_empty());
+        assert!(check_method_count(functions.len(), &config, false).is_ok());
+        let err = check_method_count(functions.len(), &config, true).unwrap_err();
+        assert!(err.to_string().contains("at least 2"));
+
+        temp_dir.close()?;
+
+        Ok(())
+    }
+
     #[test]
     fn test_is_valid_api_function() {
         // valid API function
@@ -602,7 +1878,10 @@ mod tests {
                 todo!()
             }
         };
-        assert!(is_valid_api_function(&valid_fn));
+        assert!(is_valid_api_function(
+            &valid_fn,
+            &GeneratorConfig::terminal()
+        ));
 
         // not public
         let private_fn: ItemFn = parse_quote! {
@@ -610,7 +1889,10 @@ mod tests {
                 todo!()
             }
         };
-        assert!(!is_valid_api_function(&private_fn));
+        assert!(!is_valid_api_function(
+            &private_fn,
+            &GeneratorConfig::terminal()
+        ));
 
         // not async
         let sync_fn: ItemFn = parse_quote! {
@@ -618,7 +1900,10 @@ mod tests {
                 todo!()
             }
         };
-        assert!(!is_valid_api_function(&sync_fn));
+        assert!(!is_valid_api_function(
+            &sync_fn,
+            &GeneratorConfig::terminal()
+        ));
 
         // no configuration parameter
         let no_config_fn: ItemFn = parse_quote! {
@@ -626,7 +1911,10 @@ mod tests {
                 todo!()
             }
         };
-        assert!(!is_valid_api_function(&no_config_fn));
+        assert!(!is_valid_api_function(
+            &no_config_fn,
+            &GeneratorConfig::terminal()
+        ));
     }
 
     #[test]
@@ -639,7 +1927,7 @@ mod tests {
             ) -> Result<(), Error<()>>
         };
 
-        let params = extract_parameters(&fn_sig.inputs);
+        let params = extract_parameters(&fn_sig.inputs, &GeneratorConfig::terminal());
         assert_eq!(params.len(), 2); // configuration should be filtered out
         assert_eq!(params[0].name, "param1");
         assert_eq!(params[0].ty, "String");
@@ -647,13 +1935,350 @@ mod tests {
         assert_eq!(params[1].ty, "Vec < i32 >");
     }
 
+    #[test]
+    fn test_apply_id_types() {
+        let fn_sig: syn::Signature = parse_quote! {
+            async fn get_card_by_id(configuration: &Configuration, id: &str) -> Result<(), Error<()>>
+        };
+
+        let params = apply_id_types(
+            "get_card_by_id",
+            extract_parameters(&fn_sig.inputs, &GeneratorConfig::terminal()),
+            &GeneratorConfig::terminal(),
+        );
+        assert_eq!(params[0].ty, "& models :: CardId");
+        assert_eq!(params[0].arg, "id.as_str()");
+
+        // unknown resources and non-`by_id` functions are left alone
+        let params = apply_id_types(
+            "get_widget_by_id",
+            extract_parameters(&fn_sig.inputs, &GeneratorConfig::terminal()),
+            &GeneratorConfig::terminal(),
+        );
+        assert_eq!(params[0].ty, "& str");
+        let params = apply_id_types(
+            "get_card",
+            extract_parameters(&fn_sig.inputs, &GeneratorConfig::terminal()),
+            &GeneratorConfig::terminal(),
+        );
+        assert_eq!(params[0].arg, "id");
+
+        let generated = generate_arg_list(&apply_id_types(
+            "delete_address_by_id",
+            extract_parameters(&fn_sig.inputs, &GeneratorConfig::terminal()),
+            &GeneratorConfig::terminal(),
+        ))
+        .unwrap()
+        .to_string();
+        assert_eq!(generated, ", id . as_str ()");
+    }
+
+    #[test]
+    fn test_alias_response_type() {
+        let func: ItemFn = parse_quote! {
+            pub async fn delete_cart(configuration: &Configuration) -> Result<models::DeleteAddressById200Response, Error<DeleteCartError>> {
+                todo!()
+            }
+        };
+        let func = parse_function(func, "cart_api", &GeneratorConfig::terminal()).unwrap();
+        assert_eq!(func.result_types.value, "models :: Ack");
+
+        assert_eq!(
+            alias_response_type(
+                "models :: GetCart200Response".to_string(),
+                &GeneratorConfig::terminal()
+            ),
+            "models :: GetCart200Response"
+        );
+    }
+
+    #[test]
+    fn test_sub_client_names() {
+        let function = |name: &str| ApiFunctionInfo {
+            function_name: name.to_string(),
+            method_name: name.to_string(),
+            module_name: "cart_api".to_string(),
+            parameters: vec![],
+            result_types: ResultTypesInfo {
+                value: "models :: Cart".to_string(),
+                error: "GetCartError".to_string(),
+                envelope: None,
+            },
+            documentation: String::new(),
+            feature: "test".to_string(),
+            endpoint: None,
+            error_statuses: vec![],
+        };
+        let cart = [function("get_cart"), function("put_cart_item")];
+        let cart: Vec<_> = cart.iter().collect();
+        assert_eq!(
+            module_resource("cart_api", &cart, &GeneratorConfig::terminal()),
+            "cart"
+        );
+
+        let address = [function("get_address"), function("get_address_by_id")];
+        let address: Vec<_> = address.iter().collect();
+        assert_eq!(
+            module_resource("address_api", &address, &GeneratorConfig::terminal()),
+            "address"
+        );
+
+        let misc = [function("get_view_init"), function("post_email")];
+        let misc: Vec<_> = misc.iter().collect();
+        assert_eq!(
+            module_resource("miscellaneous_api", &misc, &GeneratorConfig::terminal()),
+            "miscellaneous"
+        );
+
+        for (function_name, has_by_id, expected) in [
+            ("get_cart", false, "get"),
+            ("get_address", true, "list"),
+            ("get_address_by_id", true, "get"),
+            ("delete_address_by_id", true, "delete"),
+            ("post_order", true, "create"),
+            ("put_profile", false, "update"),
+            ("put_cart_item", false, "set_item"),
+            ("post_cart_convert", false, "convert"),
+        ] {
+            let resource = function_name.split('_').nth(1).unwrap();
+            assert_eq!(
+                sub_client_method_name(function_name, resource, has_by_id),
+                expected
+            );
+        }
+        assert_eq!(
+            sub_client_method_name("post_email", "miscellaneous", false),
+            "post_email"
+        );
+    }
+
+    #[test]
+    fn test_module_heading() {
+        assert_eq!(module_heading("cart"), "Cart");
+        assert_eq!(module_heading("app_o_auth"), "App OAuth");
+        assert_eq!(
+            module_heading("personal_access_token"),
+            "Personal Access Token"
+        );
+        assert_eq!(module_heading("x"), "X");
+
+        let config = GeneratorConfig::terminal();
+        assert!(
+            generate_module_comment("app_o_auth_api", "app", &config)
+                .starts_with("App OAuth API\n\n")
+        );
+    }
+
+    #[test]
+    fn test_method_names() -> Result<(), BuildError> {
+        let function = |name: &str| ApiFunctionInfo {
+            function_name: name.to_string(),
+            method_name: name.to_string(),
+            module_name: "address_api".to_string(),
+            parameters: vec![],
+            result_types: ResultTypesInfo {
+                value: "models :: GetAddress200Response".to_string(),
+                error: "GetAddressError".to_string(),
+                envelope: None,
+            },
+            documentation: String::new(),
+            feature: "test".to_string(),
+            endpoint: None,
+            error_statuses: vec![],
+        };
+
+        let names = parse_method_names(
+            r#"
+            [methods]
+            get_address = "list_addresses"
+            "#,
+        )?;
+        let mut functions = vec![function("get_address"), function("get_address_by_id")];
+        apply_method_names(&mut functions, &names)?;
+        assert_eq!(functions[0].method_name, "list_addresses");
+        assert_eq!(functions[1].method_name, "get_address_by_id");
+
+        let generated = generate_method(&functions[0], &GeneratorConfig::terminal())?.to_string();
+        assert!(generated.contains("pub async fn list_addresses"));
+        assert!(generated.contains("# [deprecated (note = \"renamed to `list_addresses`\")]"));
+        assert!(generated.contains("pub async fn get_address (& self) -> Result"));
+        assert!(generated.contains("self . list_addresses () . await"));
+
+        // functions that don't exist
+        let names = parse_method_names("[methods]\nget_adress = \"list_addresses\"")?;
+        let err = apply_method_names(&mut functions, &names).unwrap_err();
+        assert!(err.to_string().contains("get_adress"));
+
+        // clashing with another method
+        let names = parse_method_names("[methods]\nget_address = \"get_address_by_id\"")?;
+        assert!(apply_method_names(&mut functions, &names).is_err());
+
+        // not an identifier
+        assert!(parse_method_names("[methods]\nget_address = \"list addresses\"").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_ergonomic_params() -> Result<(), BuildError> {
+        let required = parse_required_models(
+            r#"
+            pub struct PutCartItemRequest {
+                pub product_variant_id: models::VariantId,
+                pub quantity: i32,
+            }
+            pub struct PutProfileRequest {
+                pub name: Option<String>,
+            }
+            "#,
+        )?;
+        assert_eq!(required, vec!["PutCartItemRequest"]);
+        let required: HashSet<String> = required.into_iter().collect();
+
+        let param = |name: &str, ty: &str| ParamInfo {
+            name: name.to_string(),
+            ty: ty.to_string(),
+            arg: name.to_string(),
+        };
+        let mut functions = vec![ApiFunctionInfo {
+            function_name: "put_cart_item".to_string(),
+            method_name: "put_cart_item".to_string(),
+            module_name: "cart_api".to_string(),
+            parameters: vec![
+                param("id", "& models :: CardId"),
+                param("name", "& str"),
+                param("item", "Option < models :: PutCartItemRequest >"),
+                param("profile", "Option < models :: PutProfileRequest >"),
+            ],
+            result_types: ResultTypesInfo {
+                value: "models :: PutCartItem200Response".to_string(),
+                error: "PutCartItemError".to_string(),
+                envelope: None,
+            },
+            documentation: String::new(),
+            feature: "test".to_string(),
+            endpoint: None,
+            error_statuses: vec![],
+        }];
+        ergonomic_params(&mut functions, &required);
+
+        let params: Vec<_> = functions[0]
+            .parameters
+            .iter()
+            .map(|param| (param.ty.as_str(), param.arg.as_str()))
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                ("impl AsRef < models :: CardId >", "id.as_ref().as_str()"),
+                ("impl AsRef < str >", "name.as_ref()"),
+                (
+                    "impl Into < models :: PutCartItemRequest >",
+                    "Some(item.into())"
+                ),
+                // no required fields, so the body stays optional
+                ("Option < models :: PutProfileRequest >", "profile"),
+            ]
+        );
+
+        let docs = generate_method_docs(
+            &functions[0],
+            Some("client.put_cart_item"),
+            &GeneratorConfig::terminal(),
+        );
+        assert!(docs.contains("let id = models::CardId::from(\"...\");"));
+        assert!(docs.contains("let name = \"...\";"));
+        assert!(docs.contains("let item = models::PutCartItemRequest::default();"));
+        assert!(docs.contains("client.put_cart_item(&id, name, item, profile)"));
+        assert!(!docs.contains("todo!()"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_api_trait() -> Result<(), BuildError> {
+        let mut functions = vec![ApiFunctionInfo {
+            function_name: "get_card_by_id".to_string(),
+            method_name: "get_card_by_id".to_string(),
+            module_name: "card_api".to_string(),
+            parameters: vec![ParamInfo {
+                name: "id".to_string(),
+                ty: "& models :: CardId".to_string(),
+                arg: "id.as_str()".to_string(),
+            }],
+            result_types: ResultTypesInfo {
+                value: "models :: GetCardById200Response".to_string(),
+                error: "GetCardByIdError".to_string(),
+                envelope: None,
+            },
+            documentation: "Get a credit card by ID.".to_string(),
+            feature: "test".to_string(),
+            endpoint: None,
+            error_statuses: vec![],
+        }];
+        ergonomic_params(&mut functions, &HashSet::new());
+
+        let generated = generate_api_trait(&functions, &GeneratorConfig::terminal())?.to_string();
+        assert!(generated.contains("pub trait TerminalApi : Send + Sync"));
+        // concrete, borrowed param instead of `impl AsRef<..>`
+        assert!(generated.contains(
+            "fn get_card_by_id < 'a > (& 'a self , id : & 'a models :: CardId) -> BoxFuture < 'a , Result < models :: GetCardById200Response"
+        ));
+        assert!(generated.contains("impl TerminalApi for Client"));
+        assert!(generated.contains("Box :: pin (Client :: get_card_by_id (self , id))"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_endpoints() -> Result<(), BuildError> {
+        let code = r#"
+            pub enum PutCartItemError {
+                Status400(models::ErrorResponse),
+                UnknownValue(serde_json::Value),
+            }
+
+            /// Add an item to the current user's cart.
+            pub async fn put_cart_item(configuration: &configuration::Configuration, put_cart_item_request: Option<models::PutCartItemRequest>) -> Result<models::PutCartItem200Response, Error<PutCartItemError>> {
+                let uri_str = format!("{}/cart/item", configuration.base_path);
+                let mut req_builder = configuration.client.request(reqwest::Method::PUT, &uri_str);
+                if let Some(ref token) = configuration.bearer_access_token {
+                    req_builder = req_builder.bearer_auth(token.to_owned());
+                };
+                todo!()
+            }
+        "#;
+        let mut functions = parse_module_api_functions(
+            code,
+            "cart_api",
+            &GeneratorConfig::terminal(),
+            &mut HashMap::new(),
+            &mut Vec::new(),
+        )?;
+        let endpoint = functions[0].endpoint.as_ref().unwrap();
+        assert_eq!(endpoint.body.as_deref(), Some("PutCartItemRequest"));
+        assert!(endpoint.requires_auth);
+
+        let names = parse_method_names("[methods]\nput_cart_item = \"set_cart_item\"")?;
+        apply_method_names(&mut functions, &names)?;
+        let generated = generate_endpoints(&functions, &GeneratorConfig::terminal())?;
+        assert!(generated.contains("name : \"set_cart_item\""));
+        assert!(generated.contains("operation : \"put_cart_item\""));
+        assert!(generated.contains("method : HttpMethod :: Put"));
+        assert!(generated.contains("path : \"/cart/item\""));
+        assert!(generated.contains("body : Some (\"PutCartItemRequest\")"));
+        assert!(generated.contains("error_statuses : & [400u16]"));
+        assert!(generated.contains("requires_auth : true"));
+
+        functions[0].endpoint = None;
+        assert!(generate_endpoints(&functions, &GeneratorConfig::terminal()).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_extract_result_types() {
         // simple types
         let return_type: ReturnType = parse_quote! {
             -> Result<String, Error<CustomError>>
         };
-        let result = extract_result_types(&return_type).unwrap();
+        let result = extract_result_types(&return_type, &GeneratorConfig::terminal()).unwrap();
         assert_eq!(result.value, "String");
         assert_eq!(result.error, "CustomError");
 
@@ -661,7 +2286,7 @@ mod tests {
         let complex_return: ReturnType = parse_quote! {
             -> Result<Vec<HashMap<String, i32>>, Error<ComplexError>>
         };
-        let result = extract_result_types(&complex_return).unwrap();
+        let result = extract_result_types(&complex_return, &GeneratorConfig::terminal()).unwrap();
         assert_eq!(result.value, "Vec < HashMap < String , i32 > >");
         assert_eq!(result.error, "ComplexError");
 
@@ -669,7 +2294,7 @@ mod tests {
         let invalid_return: ReturnType = parse_quote! {
             -> String
         };
-        assert!(extract_result_types(&invalid_return).is_none());
+        assert!(extract_result_types(&invalid_return, &GeneratorConfig::terminal()).is_none());
     }
 
     #[test]
@@ -677,23 +2302,31 @@ mod tests {
         let functions = vec![ApiFunctionInfo {
             module_name: "test_api".to_string(),
             function_name: "test_function".to_string(),
+            method_name: "test_function".to_string(),
             result_types: ResultTypesInfo {
                 value: "String".to_string(),
                 error: "TestError".to_string(),
+                envelope: None,
             },
             parameters: vec![ParamInfo {
                 name: "param1".to_string(),
                 ty: "i32".to_string(),
+                arg: "param1".to_string(),
             }],
             documentation: "/// Test function documentation".to_string(),
+            feature: "test".to_string(),
+            endpoint: None,
+            error_statuses: vec![],
         }];
 
-        let generated = generate_api_methods(&functions).unwrap().to_string();
+        let generated = generate_api_methods(&functions, &GeneratorConfig::terminal())
+            .unwrap()
+            .to_string();
         assert!(generated.contains("test_function"));
         assert!(generated.contains("param1 : i32"));
         assert!(
             generated
-                .contains("Result < String , apis :: Error < apis :: test_api :: TestError >>")
+                .contains("Result < String , apis :: Error < apis :: test_api :: TestError > >")
         );
         assert!(generated.contains("Test function documentation"));
     }
@@ -713,6 +2346,146 @@ mod tests {
             #[derive(Debug)]
         };
         assert_eq!(parse_enum_doc_comment(&non_doc_attrs), "");
+
+        // every line is kept, not just the first
+        let func: ItemFn = parse_quote! {
+            /// Create an order without a cart.
+            ///
+            /// The order will be placed immediately.
+            pub async fn post_order() {}
+        };
+        assert_eq!(
+            parse_enum_doc_comment(&func.attrs),
+            "Create an order without a cart.\n\nThe order will be placed immediately."
+        );
+    }
+
+    #[test]
+    fn test_parse_endpoint_and_error_statuses() -> Result<(), BuildError> {
+        let code = r#"
+            pub enum GetCardByIdError {
+                Status401(models::ErrorResponse),
+                Status404(models::ErrorResponse),
+                UnknownValue(serde_json::Value),
+            }
+
+            /// Get a credit card by ID associated with the current user.
+            pub async fn get_card_by_id(configuration: &configuration::Configuration, id: &str) -> Result<models::GetCardById200Response, Error<GetCardByIdError>> {
+                let uri_str = format!("{}/card/{id}", configuration.base_path, id=crate::apis::urlencode(id));
+                let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
+                todo!()
+            }
+        "#;
+        let functions = parse_module_api_functions(
+            code,
+            "card_api",
+            &GeneratorConfig::terminal(),
+            &mut HashMap::new(),
+            &mut Vec::new(),
+        )?;
+        let func = &functions[0];
+        assert_eq!(
+            func.endpoint,
+            Some(EndpointInfo {
+                method: "GET".to_string(),
+                path: "/card/{id}".to_string(),
+                body: None,
+                requires_auth: false,
+            })
+        );
+        assert_eq!(func.error_statuses, vec![401, 404]);
+
+        let docs = generate_method_docs(
+            func,
+            Some("client.card().get"),
+            &GeneratorConfig::terminal(),
+        );
+        assert!(docs.contains("# HTTP\n\n`GET /card/{id}`"));
+        assert!(docs.contains("- `404` Not Found"));
+        assert!(docs.contains("let id = models::CardId::from(\"...\");"));
+        assert!(docs.contains("let response = client.card().get(&id).await?;"));
+
+        assert!(
+            !generate_method_docs(func, None, &GeneratorConfig::terminal()).contains("# Example")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_envelope_types() -> Result<(), BuildError> {
+        let models = r#"
+            pub struct GetCart200Response {
+                #[serde(rename = "data")]
+                pub data: Box<models::Cart>,
+            }
+            pub struct GetAddress200Response {
+                pub data: Vec<models::Address>,
+            }
+            pub struct PostCard200Response {
+                pub data: String,
+            }
+            pub struct DeleteAddressById200Response {
+                pub data: Data,
+            }
+            pub struct Cart {
+                pub items: Vec<models::CartItem>,
+                pub data: i32,
+            }
+        "#;
+
+        let envelopes: HashMap<_, _> = parse_envelope_types(models)?.into_iter().collect();
+        assert_eq!(envelopes.len(), 3);
+        assert_eq!(
+            envelopes["GetCart200Response"],
+            EnvelopeInfo {
+                data: "models :: Cart".to_string(),
+                boxed: true,
+            }
+        );
+        assert_eq!(
+            envelopes["GetAddress200Response"].data,
+            "Vec < models :: Address >"
+        );
+        assert!(!envelopes["GetAddress200Response"].boxed);
+        assert_eq!(envelopes["PostCard200Response"].data, "String");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_unwrapped_method() -> Result<(), BuildError> {
+        let mut functions = vec![ApiFunctionInfo {
+            module_name: "cart_api".to_string(),
+            function_name: "get_cart".to_string(),
+            method_name: "get_cart".to_string(),
+            result_types: ResultTypesInfo {
+                value: "models :: GetCart200Response".to_string(),
+                error: "GetCartError".to_string(),
+                envelope: None,
+            },
+            parameters: vec![],
+            documentation: "Get the current user's cart.".to_string(),
+            feature: "test".to_string(),
+            endpoint: None,
+            error_statuses: vec![],
+        }];
+        let envelopes =
+            parse_envelope_types("pub struct GetCart200Response { pub data: Box<models::Cart> }")?
+                .into_iter()
+                .collect();
+
+        unwrap_envelopes(&mut functions, &envelopes);
+
+        let generated = generate_client_impl(&functions, &GeneratorConfig::terminal())?;
+        assert!(generated.contains(
+            "Result < models :: Cart , apis :: Error < apis :: cart_api :: GetCartError > >"
+        ));
+        assert!(generated.contains("map (| response | * response . data)"));
+        // the raw client still returns the envelope
+        assert!(generated.contains("impl RawClient < '_ >"));
+        assert!(generated.contains("Result < models :: GetCart200Response , apis :: Error"));
+
+        Ok(())
     }
 
     #[test]
@@ -720,20 +2493,77 @@ mod tests {
         let functions = vec![ApiFunctionInfo {
             module_name: "test_api".to_string(),
             function_name: "test_function".to_string(),
+            method_name: "test_function".to_string(),
             result_types: ResultTypesInfo {
                 value: "String".to_string(),
                 error: "TestError".to_string(),
+                envelope: None,
             },
             parameters: vec![],
             documentation: "Test function".to_string(),
+            feature: "test".to_string(),
+            endpoint: None,
+            error_statuses: vec![],
         }];
 
-        let impl_str = generate_client_impl(&functions).unwrap();
+        let impl_str = generate_client_impl(&functions, &GeneratorConfig::terminal()).unwrap();
         assert!(impl_str.contains("impl Client"));
         assert!(impl_str.contains("test_function"));
         assert!(
-            impl_str.contains("Result < String , apis :: Error < apis :: test_api :: TestError >>")
+            impl_str
+                .contains("Result < String , apis :: Error < apis :: test_api :: TestError > >")
         );
         assert!(impl_str.contains("Test function"));
     }
+
+    #[test]
+    fn test_generate_with_custom_config() -> Result<(), BuildError> {
+        let temp_dir = TempDir::new()?;
+        let temp_path = temp_dir.path();
+        fs::write(
+            temp_path.join("pets_service.rs"),
+            r#"
+                /// Lists pets
+                pub async fn list_pets(cfg: &Settings, limit: i32) -> Result<Vec<Pet>, ApiError<ListPetsError>> {
+                    todo!()
+                }
+            "#,
+        )?;
+        fs::write(temp_path.join("settings.rs"), "")?;
+
+        let config = GeneratorConfig {
+            client_name: "PetStore".to_string(),
+            config_type: "petstore_api::Settings".to_string(),
+            config_param: "cfg".to_string(),
+            error_type: "petstore_api::ApiError".to_string(),
+            module_path: "petstore_api::services".to_string(),
+            module_suffix: "_service".to_string(),
+            excluded_files: vec!["settings".to_string()],
+            feature_gates: true,
+            ..GeneratorConfig::new("petstore", "Petstore")
+        };
+
+        let modules = discover_api_module_names(temp_path, &config)?;
+        assert_eq!(modules, vec!["pets_service".to_string()]);
+        let (functions, skipped) = parse_api_functions(temp_path, &modules, &config)?;
+        assert!(skipped.is_empty());
+        assert_eq!(functions[0].parameters.len(), 1);
+        assert_eq!(functions[0].feature, "pets");
+
+        let generated = generate_client_impl(&functions, &config)?;
+        assert!(generated.contains("impl PetStore"));
+        assert!(generated.contains("pub fn new (config : petstore_api :: Settings)"));
+        assert!(generated.contains("impl RawPetStore"));
+        assert!(generated.contains("pub struct PetsPetStore"));
+        assert!(generated.contains("pub trait PetstoreApi"));
+        assert!(generated.contains("# [cfg (feature = \"pets\")]"));
+        assert!(generated.contains(
+            "petstore_api :: ApiError < petstore_api :: services :: pets_service :: ListPetsError >"
+        ));
+        assert!(generated.contains(
+            "petstore_api :: services :: pets_service :: list_pets (& self . config , limit)"
+        ));
+
+        Ok(())
+    }
 }
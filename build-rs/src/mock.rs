@@ -0,0 +1,194 @@
+//! a mock client for tests.
+//!
+//! `Mock{client_name}` (e.g. `MockClient`) implements the API trait with one
+//! `Expectation` per method, which tests program through `expect_{method}()`.
+//! the generated code expects an `Expectation<A, T, E>` type in scope, for
+//! calls with args `A` returning `Result<T, Error<E>>`, with `new(name)`,
+//! `call(args)` and `verify()`. it's hand-written in the `terminal` crate's
+//! `mock` module, which includes the generated code.
+
+use quote::quote;
+
+use crate::{
+    ApiFunctionInfo, BuildError, GeneratorConfig, feature_gate, method_return_type,
+    trait_method_signature, trait_param_types,
+};
+
+/// generates the mock client and its API trait impl, as a string
+pub fn generate_mock_client(
+    functions: &[ApiFunctionInfo],
+    config: &GeneratorConfig,
+) -> Result<String, BuildError> {
+    let mock_name = syn::Ident::new(
+        &format!("Mock{}", config.client_name),
+        proc_macro2::Span::call_site(),
+    );
+    let trait_name = syn::Ident::new(
+        &format!("{}Api", config.api_name),
+        proc_macro2::Span::call_site(),
+    );
+
+    let mut fields = Vec::new();
+    let mut inits = Vec::new();
+    let mut expect_methods = Vec::new();
+    let mut verifies = Vec::new();
+    let mut impl_methods = Vec::new();
+
+    for func in functions {
+        let field = syn::Ident::new(&func.method_name, proc_macro2::Span::call_site());
+        let name = &func.method_name;
+        let expect = syn::Ident::new(
+            &format!("expect_{}", func.method_name),
+            proc_macro2::Span::call_site(),
+        );
+        let return_type = method_return_type(func)?;
+        let error_type: syn::Type = syn::parse_str(&format!(
+            "{}::{}::{}",
+            config.module_path, func.module_name, func.result_types.error
+        ))?;
+
+        // calls are recorded with owned args, e.g. `String` for a `&str` param
+        let (arg_types, arg_values): (Vec<syn::Type>, Vec<proc_macro2::TokenStream>) = func
+            .parameters
+            .iter()
+            .zip(trait_param_types(func))
+            .map(|(param, ty)| {
+                let name = syn::Ident::new(&param.name, proc_macro2::Span::call_site());
+                Ok(match ty.strip_prefix('&') {
+                    Some("str") => (syn::parse_str("String")?, quote! { #name.to_owned() }),
+                    Some(borrowed) => (syn::parse_str(borrowed)?, quote! { #name.clone() }),
+                    None => (syn::parse_str(&ty)?, quote! { #name }),
+                })
+            })
+            .collect::<Result<Vec<_>, BuildError>>()?
+            .into_iter()
+            .unzip();
+        let expectation = quote! { Expectation<(#(#arg_types,)*), #return_type, #error_type> };
+
+        let expect_doc = format!(
+            "Programs the response of [`{}::{}`] and the number of calls expected",
+            trait_name, func.method_name
+        );
+        let signature = trait_method_signature(func, config)?;
+        let gate = feature_gate(func);
+
+        fields.push(quote! { #gate #field: #expectation, });
+        inits.push(quote! { #gate #field: Expectation::new(#name), });
+        expect_methods.push(quote! {
+            #[doc = #expect_doc]
+            #gate
+            pub fn #expect(&mut self) -> &mut #expectation {
+                &mut self.#field
+            }
+        });
+        verifies.push(quote! { #gate self.#field.verify(); });
+        impl_methods.push(quote! {
+            #gate
+            #signature {
+                Box::pin(std::future::ready(self.#field.call((#(#arg_values,)*))))
+            }
+        });
+    }
+
+    let mock_doc = format!(
+        "A stand-in for [`{}`] in tests, implementing [`{}`]\n\n\
+         Every method panics unless its response was programmed with the matching \
+         `expect_{{method}}()`. Calls are recorded with their arguments, and call \
+         counts set with `times` are checked by [`{}::verify`] and on drop.",
+        config.client_name, trait_name, mock_name
+    );
+
+    let mock = quote! {
+        #[doc = #mock_doc]
+        pub struct #mock_name {
+            #(#fields)*
+        }
+
+        impl #mock_name {
+            /// Creates a mock with no responses programmed
+            pub fn new() -> Self {
+                #mock_name {
+                    #(#inits)*
+                }
+            }
+
+            #(#expect_methods)*
+
+            /// Panics if a method was called a different number of times than
+            /// set with `times`
+            pub fn verify(&self) {
+                #(#verifies)*
+            }
+        }
+
+        impl Default for #mock_name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl Drop for #mock_name {
+            fn drop(&mut self) {
+                // don't turn a failing test's panic into an abort
+                if !std::thread::panicking() {
+                    self.verify();
+                }
+            }
+        }
+
+        impl #trait_name for #mock_name {
+            #(#impl_methods)*
+        }
+    };
+
+    Ok(mock.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParamInfo, ResultTypesInfo};
+
+    #[test]
+    fn test_generate_mock_client() -> Result<(), BuildError> {
+        let functions = vec![ApiFunctionInfo {
+            module_name: "order_api".to_string(),
+            function_name: "post_order".to_string(),
+            method_name: "create_order".to_string(),
+            result_types: ResultTypesInfo {
+                value: "String".to_string(),
+                error: "PostOrderError".to_string(),
+                envelope: None,
+            },
+            parameters: vec![
+                ParamInfo {
+                    name: "note".to_string(),
+                    ty: "impl AsRef < str >".to_string(),
+                    arg: "note.as_ref()".to_string(),
+                },
+                ParamInfo {
+                    name: "quantity".to_string(),
+                    ty: "i32".to_string(),
+                    arg: "quantity".to_string(),
+                },
+            ],
+            documentation: "Create an order".to_string(),
+            feature: "order".to_string(),
+            endpoint: None,
+            error_statuses: vec![],
+        }];
+
+        let generated = generate_mock_client(&functions, &GeneratorConfig::terminal())?;
+        assert!(generated.contains("pub struct MockClient"));
+        assert!(generated.contains(
+            "create_order : Expectation < (String , i32 ,) , String , apis :: order_api :: PostOrderError >"
+        ));
+        assert!(generated.contains("pub fn expect_create_order (& mut self)"));
+        assert!(generated.contains("impl TerminalApi for MockClient"));
+        assert!(
+            generated.contains("self . create_order . call ((note . to_owned () , quantity ,))")
+        );
+        assert!(generated.contains("# [cfg (feature = \"order\")]"));
+        Ok(())
+    }
+}
@@ -0,0 +1,548 @@
+//! the front-end that reads the OpenAPI document itself, which is what the
+//! client is generated from.
+//!
+//! it names functions, modules, params and models the way OpenAPI Generator's
+//! `rust` target does, so the generated client can call the checked-in
+//! `openapi` crate. `check_spec_agreement` compares its output against the
+//! functions scraped from that crate: if they disagree, the crate was
+//! generated from a different version of the spec and needs regenerating.
+
+use std::{collections::BTreeSet, fs, path::Path};
+
+use serde_json::Value;
+
+use crate::{
+    ApiFunctionInfo, BuildError, EndpointInfo, GeneratorConfig, ParamInfo, ResultTypesInfo,
+    alias_response_type, apply_id_types, features::assign_features, to_pascal_case,
+};
+
+/// in the order the generator reads a path's operations, which decides the
+/// name of an inline schema shared by several of them
+const HTTP_METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "patch", "head", "options", "trace",
+];
+
+/// reads an OpenAPI 3 document (`.json`, `.yaml` or `.yml`) and returns one
+/// `ApiFunctionInfo` per operation, sorted and with features assigned like
+/// `parse_api_functions`
+pub fn load_openapi_document(
+    path: &Path,
+    config: &GeneratorConfig,
+) -> Result<Vec<ApiFunctionInfo>, BuildError> {
+    let source = fs::read_to_string(path)?;
+    let document: Value = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("yaml" | "yml") => serde_yaml::from_str(&source)?,
+        _ => serde_json::from_str(&source)?,
+    };
+    parse_openapi_document(&document, config)
+}
+
+pub(crate) fn parse_openapi_document(
+    document: &Value,
+    config: &GeneratorConfig,
+) -> Result<Vec<ApiFunctionInfo>, BuildError> {
+    let paths = document
+        .get("paths")
+        .and_then(Value::as_object)
+        .ok_or("OpenAPI document has no `paths`")?;
+    let global_security = document.get("security");
+
+    let mut inline_schemas = InlineSchemas::default();
+    let mut functions = Vec::new();
+    for (path, item) in paths {
+        for method in HTTP_METHODS {
+            let Some(operation) = item.get(*method) else {
+                continue;
+            };
+            functions.push(parse_operation(
+                path,
+                method,
+                operation,
+                global_security,
+                &mut inline_schemas,
+                config,
+            )?);
+        }
+    }
+
+    functions.sort_by(|a, b| {
+        a.module_name
+            .cmp(&b.module_name)
+            .then(a.function_name.cmp(&b.function_name))
+    });
+    assign_features(&mut functions, config);
+
+    Ok(functions)
+}
+
+/// names request and response schemas the way the generator does: a `$ref`
+/// by the schema it points to, and an inline schema after the first operation
+/// using it, in document order. so every endpoint answering with the same
+/// `{ "data": "ok" }` as `DELETE /address/{id}` returns `DeleteAddressById200Response`
+#[derive(Default)]
+struct InlineSchemas(Vec<(Value, String)>);
+
+impl InlineSchemas {
+    fn name(&mut self, schema: &Value, name: String) -> String {
+        if let Some(model) = schema_name(schema) {
+            return model;
+        }
+        if let Some((_, first)) = self.0.iter().find(|(seen, _)| seen == schema) {
+            return first.clone();
+        }
+        self.0.push((schema.clone(), name.clone()));
+        name
+    }
+}
+
+fn parse_operation(
+    path: &str,
+    method: &str,
+    operation: &Value,
+    global_security: Option<&Value>,
+    inline_schemas: &mut InlineSchemas,
+    config: &GeneratorConfig,
+) -> Result<ApiFunctionInfo, BuildError> {
+    let function_name = match operation.get("operationId").and_then(Value::as_str) {
+        Some(operation_id) => to_snake_case(operation_id),
+        None => default_operation_name(path, method),
+    };
+    let type_prefix = to_pascal_case(&function_name);
+
+    let module_name = match operation
+        .get("tags")
+        .and_then(|tags| tags.get(0))
+        .and_then(Value::as_str)
+    {
+        Some(tag) => format!("{}_api", to_snake_case(&camelize(tag))),
+        None => "default_api".to_string(),
+    };
+
+    let mut parameters: Vec<ParamInfo> = operation
+        .get("parameters")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|param| param.get("in").and_then(Value::as_str) == Some("path"))
+        .filter_map(|param| param.get("name").and_then(Value::as_str))
+        .map(|name| {
+            let name = to_snake_case(name);
+            ParamInfo {
+                arg: name.clone(),
+                name,
+                ty: "& str".to_string(),
+            }
+        })
+        .collect();
+
+    let body = operation
+        .pointer("/requestBody/content/application~1json/schema")
+        .map(|schema| inline_schemas.name(schema, format!("{}Request", type_prefix)));
+    if let Some(body) = &body {
+        let name = to_snake_case(body);
+        parameters.push(ParamInfo {
+            arg: name.clone(),
+            name,
+            ty: format!("Option < models :: {} >", body),
+        });
+    }
+    let parameters = apply_id_types(&function_name, parameters, config);
+
+    let responses = operation.get("responses").and_then(Value::as_object);
+    let value = responses
+        .and_then(|responses| responses.get("200"))
+        .and_then(|response| response.pointer("/content/application~1json/schema"))
+        .map(|schema| inline_schemas.name(schema, format!("{}200Response", type_prefix)))
+        .map(|model| alias_response_type(format!("models :: {}", model), config))
+        .unwrap_or_else(|| "()".to_string());
+    let error_statuses = responses
+        .into_iter()
+        .flat_map(|responses| responses.keys())
+        .filter_map(|status| status.parse::<u16>().ok())
+        .filter(|status| !(200..300).contains(status))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let documentation = ["description", "summary"]
+        .iter()
+        .find_map(|key| operation.get(*key).and_then(Value::as_str))
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+
+    // an operation's own `security` overrides the document's, and `[]` or `[{}]` means none
+    let requires_auth = operation
+        .get("security")
+        .or(global_security)
+        .and_then(Value::as_array)
+        .is_some_and(|requirements| {
+            requirements
+                .iter()
+                .any(|requirement| requirement.as_object().is_some_and(|r| !r.is_empty()))
+        });
+
+    Ok(ApiFunctionInfo {
+        module_name,
+        method_name: function_name.clone(),
+        result_types: ResultTypesInfo {
+            value,
+            error: format!("{}Error", type_prefix),
+            envelope: None,
+        },
+        function_name,
+        parameters,
+        documentation,
+        feature: String::new(),
+        endpoint: Some(EndpointInfo {
+            method: method.to_uppercase(),
+            path: path.to_string(),
+            body,
+            requires_auth,
+        }),
+        error_statuses,
+    })
+}
+
+/// compares the functions read from the OpenAPI document with the ones scraped
+/// from the `openapi` crate, and fails listing every difference: operations
+/// only one side has, and differing HTTP method, path, auth, params, body,
+/// response model or error statuses.
+pub fn check_spec_agreement(
+    spec: &[ApiFunctionInfo],
+    generated: &[ApiFunctionInfo],
+) -> Result<(), BuildError> {
+    let mut problems = Vec::new();
+
+    for func in spec {
+        let Some(other) = generated
+            .iter()
+            .find(|other| other.function_name == func.function_name)
+        else {
+            problems.push(format!(
+                "`{}` is in the spec but not in the `openapi` crate",
+                func.function_name
+            ));
+            continue;
+        };
+
+        if func.module_name != other.module_name {
+            problems.push(format!(
+                "`{}` is in `{}` in the spec but in `{}` in the `openapi` crate",
+                func.function_name, func.module_name, other.module_name
+            ));
+        }
+        if func.endpoint != other.endpoint {
+            problems.push(format!(
+                "`{}` is {} in the spec but {} in the `openapi` crate",
+                func.function_name,
+                describe_endpoint(func.endpoint.as_ref()),
+                describe_endpoint(other.endpoint.as_ref())
+            ));
+        }
+        let params = |func: &ApiFunctionInfo| -> Vec<String> {
+            func.parameters
+                .iter()
+                .map(|p| format!("{}: {}", p.name, p.ty))
+                .collect()
+        };
+        if params(func) != params(other) {
+            problems.push(format!(
+                "`{}` takes {:?} in the spec but {:?} in the `openapi` crate",
+                func.function_name,
+                params(func),
+                params(other)
+            ));
+        }
+        if func.result_types.value != other.result_types.value {
+            problems.push(format!(
+                "`{}` returns `{}` in the spec but `{}` in the `openapi` crate",
+                func.function_name, func.result_types.value, other.result_types.value
+            ));
+        }
+        if func.error_statuses != other.error_statuses {
+            problems.push(format!(
+                "`{}` has error statuses {:?} in the spec but {:?} in the `openapi` crate",
+                func.function_name, func.error_statuses, other.error_statuses
+            ));
+        }
+    }
+
+    for other in generated {
+        if !spec
+            .iter()
+            .any(|func| func.function_name == other.function_name)
+        {
+            problems.push(format!(
+                "`{}` is in the `openapi` crate but not in the spec",
+                other.function_name
+            ));
+        }
+    }
+
+    if problems.is_empty() {
+        return Ok(());
+    }
+
+    Err(format!(
+        "the OpenAPI document and the `openapi` crate disagree, regenerate the crate:\n  - {}",
+        problems.join("\n  - ")
+    )
+    .into())
+}
+
+fn describe_endpoint(endpoint: Option<&EndpointInfo>) -> String {
+    match endpoint {
+        Some(endpoint) => format!(
+            "`{} {}` (body: {}, auth: {})",
+            endpoint.method,
+            endpoint.path,
+            endpoint.body.as_deref().unwrap_or("none"),
+            endpoint.requires_auth
+        ),
+        None => "an unknown request".to_string(),
+    }
+}
+
+/// the model name of a `$ref` schema, e.g. `Cart` for `#/components/schemas/Cart`
+fn schema_name(schema: &Value) -> Option<String> {
+    schema
+        .get("$ref")?
+        .as_str()?
+        .rsplit('/')
+        .next()
+        .map(str::to_string)
+}
+
+/// the generator's name for an operation without an `operationId`:
+/// the method followed by the path, with `{id}` becoming `by_id`,
+/// e.g. `GET /address/{id}` -> `get_address_by_id`
+fn default_operation_name(path: &str, method: &str) -> String {
+    let segments =
+        path.split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(
+                |segment| match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                    Some(param) => format!("by_{}", to_snake_case(param)),
+                    None => to_snake_case(segment),
+                },
+            );
+
+    std::iter::once(method.to_string())
+        .chain(segments)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// joins the words of a tag into one `PascalCase` word like the generator does,
+/// e.g. `App (OAuth)` -> `AppOAuth`
+fn camelize(tag: &str) -> String {
+    tag.split(|c: char| !c.is_ascii_alphanumeric())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// e.g. `AppOAuth` -> `app_o_auth`, `getCartItem` -> `get_cart_item`, `put-cart` -> `put_cart`
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !snake.is_empty() && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            continue;
+        }
+        if c.is_ascii_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+            let starts_word = previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase() && next_is_lower);
+            if starts_word && !snake.ends_with('_') {
+                snake.push('_');
+            }
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+
+    snake.trim_end_matches('_').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn document() -> Value {
+        json!({
+            "openapi": "3.1.0",
+            "security": [{ "Bearer": [] }],
+            "paths": {
+                "/card/{id}": {
+                    "get": {
+                        "tags": ["Card"],
+                        "description": "Get a credit card by ID associated with the current user.",
+                        "parameters": [{ "name": "id", "in": "path", "required": true }],
+                        "responses": {
+                            "200": { "content": { "application/json": { "schema": { "type": "object" } } } },
+                            "404": {},
+                            "401": {}
+                        }
+                    }
+                },
+                "/cart/item": {
+                    "put": {
+                        "tags": ["Cart"],
+                        "requestBody": { "content": { "application/json": { "schema": {
+                            "type": "object",
+                            "properties": { "quantity": { "type": "integer" } }
+                        } } } },
+                        "responses": { "200": {}, "400": {} }
+                    }
+                },
+                "/app": {
+                    "post": {
+                        "tags": ["App (OAuth)"],
+                        "operationId": "createApp",
+                        "security": [],
+                        "requestBody": { "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/PostAppRequest" }
+                        } } },
+                        "responses": { "200": {} }
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_naming() {
+        assert_eq!(to_snake_case("AppOAuth"), "app_o_auth");
+        assert_eq!(to_snake_case("getCartItem"), "get_cart_item");
+        assert_eq!(to_snake_case("PutCartItemRequest"), "put_cart_item_request");
+        assert_eq!(camelize("App (OAuth)"), "AppOAuth");
+        assert_eq!(
+            default_operation_name("/address/{id}", "delete"),
+            "delete_address_by_id"
+        );
+    }
+
+    #[test]
+    fn test_parse_openapi_document() -> Result<(), BuildError> {
+        let functions = parse_openapi_document(&document(), &GeneratorConfig::terminal())?;
+        let names: Vec<_> = functions.iter().map(|f| f.function_name.as_str()).collect();
+        assert_eq!(names, vec!["create_app", "get_card_by_id", "put_cart_item"]);
+
+        let create_app = &functions[0];
+        assert_eq!(create_app.module_name, "app_o_auth_api");
+        assert_eq!(create_app.parameters[0].name, "post_app_request");
+        assert!(!create_app.endpoint.as_ref().unwrap().requires_auth);
+
+        let get_card = &functions[1];
+        assert_eq!(get_card.module_name, "card_api");
+        assert_eq!(
+            get_card.result_types.value,
+            "models :: GetCardById200Response"
+        );
+        assert_eq!(get_card.result_types.error, "GetCardByIdError");
+        assert_eq!(get_card.parameters[0].ty, "& models :: CardId");
+        assert_eq!(get_card.error_statuses, vec![401, 404]);
+        let endpoint = get_card.endpoint.as_ref().unwrap();
+        assert_eq!(
+            (endpoint.method.as_str(), endpoint.path.as_str()),
+            ("GET", "/card/{id}")
+        );
+        assert!(endpoint.requires_auth);
+
+        let put_cart_item = &functions[2];
+        assert_eq!(
+            put_cart_item.parameters[0].ty,
+            "Option < models :: PutCartItemRequest >"
+        );
+        assert_eq!(put_cart_item.parameters[0].name, "put_cart_item_request");
+        Ok(())
+    }
+
+    #[test]
+    fn test_shared_inline_schemas() -> Result<(), BuildError> {
+        let ack = json!({ "content": { "application/json": { "schema": {
+            "type": "object",
+            "properties": { "data": { "type": "string", "enum": ["ok"] } }
+        } } } });
+        let document = json!({
+            "paths": {
+                "/cart": { "delete": { "tags": ["Cart"], "responses": { "200": ack } } },
+                "/address/{id}": {
+                    "get": { "tags": ["Address"], "responses": { "200": {} } },
+                    "delete": { "tags": ["Address"], "responses": { "200": ack } }
+                }
+            }
+        });
+
+        let functions = parse_openapi_document(&document, &GeneratorConfig::terminal())?;
+        let returns = |name: &str| {
+            functions
+                .iter()
+                .find(|func| func.function_name == name)
+                .map(|func| func.result_types.value.clone())
+        };
+        // `/cart` comes first, so the schema is named after its operation
+        assert_eq!(
+            returns("delete_cart").as_deref(),
+            Some("models :: DeleteCart200Response")
+        );
+        assert_eq!(
+            returns("delete_address_by_id").as_deref(),
+            Some("models :: DeleteCart200Response")
+        );
+        assert_eq!(functions[0].feature, "address");
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_spec_agreement() -> Result<(), BuildError> {
+        let spec = parse_openapi_document(&document(), &GeneratorConfig::terminal())?;
+        assert!(
+            check_spec_agreement(
+                &spec,
+                &parse_openapi_document(&document(), &GeneratorConfig::terminal())?
+            )
+            .is_ok()
+        );
+
+        let mut changed = document();
+        changed["paths"]["/card/{id}"]["get"]["responses"]
+            .as_object_mut()
+            .unwrap()
+            .remove("404");
+        changed["paths"]["/card/{id}"]["get"]["responses"]["200"]["content"]["application/json"]
+            ["schema"] = json!({ "$ref": "#/components/schemas/Card" });
+        changed["paths"]["/cart/item"]["post"] = changed["paths"]["/cart/item"]["put"].take();
+        changed["paths"]["/cart/item"]
+            .as_object_mut()
+            .unwrap()
+            .remove("put");
+        let generated = parse_openapi_document(&changed, &GeneratorConfig::terminal())?;
+
+        let err = check_spec_agreement(&spec, &generated)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("`get_card_by_id` has error statuses [401, 404] in the spec but [401]")
+        );
+        assert!(err.contains(
+            "`get_card_by_id` returns `models :: GetCardById200Response` in the spec but `models :: Card`"
+        ));
+        assert!(err.contains("`put_cart_item` is in the spec but not in the `openapi` crate"));
+        assert!(err.contains("`post_cart_item` is in the `openapi` crate but not in the spec"));
+        Ok(())
+    }
+}
@@ -1,22 +1,92 @@
 use std::{env, fs::File, io::Write, path::Path};
 
 use build_rs::{
-    build_print_info, discover_api_module_names, generate_client_impl, parse_api_functions,
-    BuildError,
+    apply_method_names, build_print_info, check_feature_gates, check_method_count,
+    check_spec_agreement, discover_api_module_names, discover_envelope_types,
+    discover_required_models, ergonomic_params, generate_client_impl, generate_endpoints,
+    generate_mock_client, load_method_names, load_openapi_document, parse_api_functions,
+    report_skipped, strict_mode, unwrap_envelopes, BuildError, GeneratorConfig,
+    STRICT_MODE_ENV_VAR,
 };
 
+/// the ID newtype the `id` param of each resource's `*_by_id` functions takes
+const ID_TYPES: &[(&str, &str)] = &[
+    ("address", "AddressId"),
+    ("app", "AppId"),
+    ("card", "CardId"),
+    ("order", "OrderId"),
+    ("product", "ProductId"),
+    ("subscription", "SubscriptionId"),
+    ("token", "TokenId"),
+];
+
+/// codegen deduplicates identical response schemas under the name of the first
+/// endpoint that uses them, so e.g. every endpoint that just acknowledges success
+/// returns `DeleteAddressById200Response`
+const RESPONSE_TYPE_ALIASES: &[(&str, &str)] =
+    &[("models :: DeleteAddressById200Response", "models :: Ack")];
+
+/// the number of API functions in the `openapi` crate. raise it when endpoints
+/// are added; strict builds fail if fewer are found
+const MIN_METHODS: usize = 36;
+
+fn generator_config() -> GeneratorConfig {
+    let pairs = |pairs: &[(&str, &str)]| {
+        pairs
+            .iter()
+            .map(|(from, to)| (from.to_string(), to.to_string()))
+            .collect()
+    };
+
+    GeneratorConfig {
+        id_types: pairs(ID_TYPES),
+        response_type_aliases: pairs(RESPONSE_TYPE_ALIASES),
+        feature_gates: true,
+        min_methods: Some(MIN_METHODS),
+        ..GeneratorConfig::new("terminal", "Terminal")
+    }
+}
+
 fn main() -> Result<(), BuildError> {
+    let spec_path = Path::new("./openapi.json");
     let apis_folder = Path::new("./openapi/src/apis");
+    let models_folder = Path::new("./openapi/src/models");
+    let method_names_path = Path::new("./method_names.toml");
+    println!("cargo:rerun-if-changed={}", spec_path.display());
     println!("cargo:rerun-if-changed={}", apis_folder.display());
+    println!("cargo:rerun-if-changed={}", models_folder.display());
+    println!("cargo:rerun-if-changed={}", method_names_path.display());
+    println!("cargo:rerun-if-env-changed={}", STRICT_MODE_ENV_VAR);
 
     let out_dir = env::var("OUT_DIR")?;
     let dest_path = Path::new(&out_dir).join("api_methods_gen.rs");
-    let api_modules = discover_api_module_names(apis_folder)?;
-    let functions = parse_api_functions(apis_folder, &api_modules)?;
-    let impl_block = generate_client_impl(&functions)?;
+    let config = generator_config();
+    let mut functions = load_openapi_document(spec_path, &config)?;
+
+    // the client calls the `openapi` crate, so it must have been generated from the same spec
+    let api_modules = discover_api_module_names(apis_folder, &config)?;
+    let (generated, skipped) = parse_api_functions(apis_folder, &api_modules, &config)?;
+    report_skipped(&skipped, strict_mode())?;
+    check_spec_agreement(&functions, &generated)?;
+    check_method_count(generated.len(), &config, strict_mode())?;
+
+    check_feature_gates(apis_folder, models_folder, &functions)?;
+
+    let envelopes = discover_envelope_types(models_folder)?;
+    unwrap_envelopes(&mut functions, &envelopes);
+    let required_models = discover_required_models(models_folder)?;
+    ergonomic_params(&mut functions, &required_models);
+    apply_method_names(&mut functions, &load_method_names(method_names_path)?)?;
+    let impl_block = generate_client_impl(&functions, &config)?;
 
     File::create(&dest_path)?.write_all(impl_block.as_bytes())?;
 
+    let endpoints = generate_endpoints(&functions, &config)?;
+    File::create(Path::new(&out_dir).join("endpoints_gen.rs"))?.write_all(endpoints.as_bytes())?;
+
+    let mock = generate_mock_client(&functions, &config)?;
+    File::create(Path::new(&out_dir).join("mock_gen.rs"))?.write_all(mock.as_bytes())?;
+
     build_print_info(&format!(
         "Generated client for {} API functions",
         functions.len()
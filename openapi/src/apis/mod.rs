@@ -111,15 +111,25 @@ impl From<&str> for ContentType {
     }
 }
 
+#[cfg(feature = "address")]
 pub mod address_api;
+#[cfg(feature = "app")]
 pub mod app_o_auth_api;
+#[cfg(feature = "card")]
 pub mod card_api;
+#[cfg(feature = "cart")]
 pub mod cart_api;
+#[cfg(feature = "miscellaneous")]
 pub mod miscellaneous_api;
+#[cfg(feature = "order")]
 pub mod order_api;
+#[cfg(feature = "product")]
 pub mod product_api;
+#[cfg(feature = "profile")]
 pub mod profile_api;
+#[cfg(feature = "subscription")]
 pub mod subscription_api;
+#[cfg(feature = "token")]
 pub mod token_api;
 
 pub mod configuration;
@@ -16,7 +16,7 @@ use serde::{Deserialize, Serialize};
 pub struct Address {
     /// Unique object identifier. The format and length of IDs may change over time.
     #[serde(rename = "id")]
-    pub id: String,
+    pub id: models::AddressId,
     /// The recipient's name.
     #[serde(rename = "name")]
     pub name: String,
@@ -34,7 +34,7 @@ pub struct Address {
     pub province: Option<String>,
     /// ISO 3166-1 alpha-2 country code of the address.
     #[serde(rename = "country")]
-    pub country: String,
+    pub country: models::Country,
     /// Zip code of the address.
     #[serde(rename = "zip")]
     pub zip: String,
@@ -45,7 +45,7 @@ pub struct Address {
 
 impl Address {
     /// Physical address associated with a Terminal shop user.
-    pub fn new(id: String, name: String, street1: String, city: String, country: String, zip: String) -> Address {
+    pub fn new(id: models::AddressId, name: String, street1: String, city: String, country: models::Country, zip: String) -> Address {
         Address {
             id,
             name,
@@ -16,7 +16,7 @@ use serde::{Deserialize, Serialize};
 pub struct App {
     /// Unique object identifier. The format and length of IDs may change over time.
     #[serde(rename = "id")]
-    pub id: String,
+    pub id: models::AppId,
     /// Name of the app.
     #[serde(rename = "name")]
     pub name: String,
@@ -30,7 +30,7 @@ pub struct App {
 
 impl App {
     /// A Terminal App used for configuring an OAuth 2.0 client.
-    pub fn new(id: String, name: String, redirect_uri: String, secret: String) -> App {
+    pub fn new(id: models::AppId, name: String, redirect_uri: String, secret: String) -> App {
         App {
             id,
             name,
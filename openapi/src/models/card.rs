@@ -8,6 +8,9 @@
  * Generated by: https://openapi-generator.tech
  */
 
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
 use crate::models;
 use serde::{Deserialize, Serialize};
 
@@ -16,10 +19,10 @@ use serde::{Deserialize, Serialize};
 pub struct Card {
     /// Unique object identifier. The format and length of IDs may change over time.
     #[serde(rename = "id")]
-    pub id: String,
+    pub id: models::CardId,
     /// Brand of the card.
     #[serde(rename = "brand")]
-    pub brand: String,
+    pub brand: models::CardBrand,
     #[serde(rename = "expiration")]
     pub expiration: Box<models::CardExpiration>,
     /// Last four digits of the card.
@@ -29,7 +32,7 @@ pub struct Card {
 
 impl Card {
     /// Credit card used for payments in the Terminal shop.
-    pub fn new(id: String, brand: String, expiration: models::CardExpiration, last4: String) -> Card {
+    pub fn new(id: models::CardId, brand: models::CardBrand, expiration: models::CardExpiration, last4: String) -> Card {
         Card {
             id,
             brand,
@@ -37,5 +40,34 @@ impl Card {
             last4,
         }
     }
+
+    /// Whether the card's expiration month has passed, see [`models::CardExpiration::is_expired`].
+    pub fn is_expired(&self, today: SystemTime) -> bool {
+        self.expiration.is_expired(today)
+    }
+
+    /// Whether the card is still valid but expires within `window`, see
+    /// [`models::CardExpiration::expires_within`].
+    pub fn expires_within(&self, window: Duration, today: SystemTime) -> bool {
+        self.expiration.expires_within(window, today)
+    }
+
+    /// The card with all but its last four digits hidden, e.g. `Visa •••• 4242, exp 04/27`.
+    pub fn masked(&self) -> String {
+        format!(
+            "{} •••• {}, exp {:02}/{:02}",
+            self.brand,
+            self.last4,
+            self.expiration.month,
+            self.expiration.year.rem_euclid(100)
+        )
+    }
+}
+
+impl fmt::Display for Card {
+    /// Formats the card as [`Card::masked`] does.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.masked())
+    }
 }
 
@@ -0,0 +1,21 @@
+/*
+ * Terminal API
+ *
+ * Hand-maintained: this file is listed in `.openapi-generator-ignore` and is
+ * not produced by OpenAPI Generator.
+ */
+
+open_enum! {
+    /// CardBrand : Brand of a credit card, as reported by the payment processor.
+    #[derive(Default)]
+    pub enum CardBrand {
+        #[default]
+        Visa => "Visa",
+        Mastercard => "Mastercard",
+        AmericanExpress => "American Express",
+        Discover => "Discover",
+        DinersClub => "Diners Club",
+        Jcb => "JCB",
+        UnionPay => "UnionPay",
+    }
+}
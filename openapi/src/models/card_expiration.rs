@@ -1,13 +1,12 @@
 /*
  * Terminal API
  *
- * The Terminal API gives you access to the same API that powers the award winning Terminal SSH shop (`ssh terminal.shop`).
- *
- * The version of the OpenAPI document: 0.1.0
- * 
- * Generated by: https://openapi-generator.tech
+ * Hand-maintained: this file is listed in `.openapi-generator-ignore` and is
+ * not produced by OpenAPI Generator.
  */
 
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 use crate::models;
 use serde::{Deserialize, Serialize};
 
@@ -30,5 +29,107 @@ impl CardExpiration {
             month,
         }
     }
+
+    /// The moment the card stops working: the start of the month after the
+    /// expiration month, in UTC. `None` if the month isn't between 1 and 12.
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        if !(1..=12).contains(&self.month) {
+            return None;
+        }
+        // months are 1-based; roll December over into January of the next year
+        let year = i64::from(self.year);
+        let (year, month) = if self.month == 12 {
+            (year + 1, 1)
+        } else {
+            (year, self.month as u32 + 1)
+        };
+        let secs = days_from_civil(year, month, 1) * 86_400;
+        Some(if secs >= 0 {
+            UNIX_EPOCH + Duration::from_secs(secs as u64)
+        } else {
+            UNIX_EPOCH - Duration::from_secs(secs.unsigned_abs())
+        })
+    }
+
+    /// Whether the card's expiration month has passed. Cards are valid through
+    /// the last day of their expiration month, and a card with an invalid
+    /// month is treated as expired.
+    pub fn is_expired(&self, today: SystemTime) -> bool {
+        self.expires_at().is_none_or(|expires_at| today >= expires_at)
+    }
+
+    /// Whether the card is still valid but expires within `window`. A window
+    /// reaching past the latest representable time has no upper bound.
+    pub fn expires_within(&self, window: Duration, today: SystemTime) -> bool {
+        let Some(expires_at) = self.expires_at().filter(|expires_at| today < *expires_at) else {
+            return false;
+        };
+        today
+            .checked_add(window)
+            .is_none_or(|end| expires_at <= end)
+    }
+}
+
+/// days since 1970-01-01 for a proleptic Gregorian date, after Howard Hinnant's
+/// `days_from_civil` (<https://howardhinnant.github.io/date_algorithms.html>).
+/// public for `terminal::testing::fixtures`, not part of the API
+#[doc(hidden)]
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// midnight UTC on the given date
+    fn date(year: i64, month: u32, day: u32) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(days_from_civil(year, month, day) as u64 * 86_400)
+    }
+
+    #[test]
+    fn test_days_from_civil() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(2027, 5, 1), 20_939);
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let expiration = CardExpiration::new(2027, 4);
+        assert!(!expiration.is_expired(date(2027, 4, 30)));
+        assert!(expiration.is_expired(date(2027, 5, 1)));
+
+        let december = CardExpiration::new(2026, 12);
+        assert_eq!(december.expires_at(), Some(date(2027, 1, 1)));
+    }
+
+    #[test]
+    fn test_invalid_month_is_expired() {
+        let long_ago = date(1970, 1, 1);
+        for month in [0, 13, -1] {
+            let expiration = CardExpiration::new(2099, month);
+            assert_eq!(expiration.expires_at(), None);
+            assert!(expiration.is_expired(long_ago));
+            assert!(!expiration.expires_within(Duration::MAX, long_ago));
+        }
+    }
+
+    #[test]
+    fn test_expires_within() {
+        let expiration = CardExpiration::new(2027, 4);
+        let month = Duration::from_secs(31 * 86_400);
+        assert!(expiration.expires_within(month, date(2027, 4, 15)));
+        assert!(!expiration.expires_within(month, date(2027, 2, 1)));
+        assert!(!expiration.expires_within(month, date(2027, 6, 1)));
+        assert!(expiration.expires_within(Duration::MAX, date(2027, 2, 1)));
+        assert!(!expiration.expires_within(Duration::MAX, date(2027, 6, 1)));
+    }
 }
 
@@ -19,13 +19,13 @@ pub struct Cart {
     pub items: Vec<models::CartItem>,
     /// The subtotal of all items in the current user's cart, in cents (USD).
     #[serde(rename = "subtotal")]
-    pub subtotal: i32,
+    pub subtotal: models::Money,
     /// ID of the shipping address selected on the current user's cart.
     #[serde(rename = "addressID", skip_serializing_if = "Option::is_none")]
-    pub address_id: Option<String>,
+    pub address_id: Option<models::AddressId>,
     /// ID of the card selected on the current user's cart.
     #[serde(rename = "cardID", skip_serializing_if = "Option::is_none")]
-    pub card_id: Option<String>,
+    pub card_id: Option<models::CardId>,
     #[serde(rename = "amount")]
     pub amount: Box<models::CartAmount>,
     #[serde(rename = "shipping", skip_serializing_if = "Option::is_none")]
@@ -34,7 +34,7 @@ pub struct Cart {
 
 impl Cart {
     /// The current Terminal shop user's cart.
-    pub fn new(items: Vec<models::CartItem>, subtotal: i32, amount: models::CartAmount) -> Cart {
+    pub fn new(items: Vec<models::CartItem>, subtotal: models::Money, amount: models::CartAmount) -> Cart {
         Cart {
             items,
             subtotal,
@@ -16,18 +16,18 @@ use serde::{Deserialize, Serialize};
 pub struct CartAmount {
     /// Subtotal of the current user's cart, in cents (USD).
     #[serde(rename = "subtotal")]
-    pub subtotal: i32,
+    pub subtotal: models::Money,
     /// Shipping amount of the current user's cart, in cents (USD).
     #[serde(rename = "shipping", skip_serializing_if = "Option::is_none")]
-    pub shipping: Option<i32>,
+    pub shipping: Option<models::Money>,
     /// Total amount after any discounts, in cents (USD).
     #[serde(rename = "total", skip_serializing_if = "Option::is_none")]
-    pub total: Option<i32>,
+    pub total: Option<models::Money>,
 }
 
 impl CartAmount {
     /// The subtotal and shipping amounts for the current user's cart.
-    pub fn new(subtotal: i32) -> CartAmount {
+    pub fn new(subtotal: models::Money) -> CartAmount {
         CartAmount {
             subtotal,
             shipping: None,
@@ -19,18 +19,18 @@ pub struct CartItem {
     pub id: String,
     /// ID of the product variant for this item in the current user's cart.
     #[serde(rename = "productVariantID")]
-    pub product_variant_id: String,
+    pub product_variant_id: models::VariantId,
     /// Quantity of the item in the current user's cart.
     #[serde(rename = "quantity")]
     pub quantity: i32,
     /// Subtotal of the item in the current user's cart, in cents (USD).
     #[serde(rename = "subtotal")]
-    pub subtotal: i32,
+    pub subtotal: models::Money,
 }
 
 impl CartItem {
     /// An item in the current Terminal shop user's cart.
-    pub fn new(id: String, product_variant_id: String, quantity: i32, subtotal: i32) -> CartItem {
+    pub fn new(id: String, product_variant_id: models::VariantId, quantity: i32, subtotal: models::Money) -> CartItem {
         CartItem {
             id,
             product_variant_id,
@@ -0,0 +1,591 @@
+/*
+ * Terminal API
+ *
+ * Hand-maintained: this file is listed in `.openapi-generator-ignore` and is
+ * not produced by OpenAPI Generator.
+ */
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::country_data::{CountryData, CA_PROVINCES, COUNTRIES, US_STATES};
+
+/// Postal code formats for countries that have a fixed one.
+/// `#` is a digit, `@` a letter, `*` either, and anything else is literal.
+/// Spaces are optional in the input and are inserted when normalizing.
+const POSTAL_FORMATS: &[(&str, &[&str])] = &[
+    ("AT", &["####"]),
+    ("AU", &["####"]),
+    ("BE", &["####"]),
+    ("BG", &["####"]),
+    ("BR", &["#####-###"]),
+    ("CA", &["@#@ #@#"]),
+    ("CH", &["####"]),
+    ("CN", &["######"]),
+    ("CZ", &["### ##"]),
+    ("DE", &["#####"]),
+    ("DK", &["####"]),
+    ("EE", &["#####"]),
+    ("ES", &["#####"]),
+    ("FI", &["#####"]),
+    ("FR", &["#####"]),
+    (
+        "GB",
+        &[
+            "@# #@@", "@## #@@", "@@# #@@", "@@## #@@", "@#@ #@@", "@@#@ #@@",
+        ],
+    ),
+    ("GR", &["### ##"]),
+    ("HR", &["#####"]),
+    ("HU", &["####"]),
+    ("IE", &["@## ****"]),
+    ("IN", &["######"]),
+    ("IS", &["###"]),
+    ("IT", &["#####"]),
+    ("JP", &["###-####"]),
+    ("KR", &["#####"]),
+    ("LT", &["LT-#####", "#####"]),
+    ("LU", &["L-####", "####"]),
+    ("LV", &["LV-####"]),
+    ("MX", &["#####"]),
+    ("NL", &["#### @@"]),
+    ("NO", &["####"]),
+    ("NZ", &["####"]),
+    ("PL", &["##-###"]),
+    ("PT", &["####-###"]),
+    ("RO", &["######"]),
+    ("SE", &["### ##"]),
+    ("SG", &["######"]),
+    ("SI", &["####"]),
+    ("SK", &["### ##"]),
+    ("US", &["#####", "#####-####"]),
+    ("ZA", &["####"]),
+];
+
+/// Returned when an address field isn't valid for its country
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressError {
+    /// not an ISO 3166-1 alpha-2 code
+    UnknownCountry(String),
+    /// the postal code doesn't match any format used in the country
+    InvalidPostalCode { country: CountryCode, zip: String },
+    /// the country requires a province or state, but none was given
+    MissingProvince { country: CountryCode },
+    /// the province or state isn't one of the country's subdivisions
+    InvalidProvince {
+        country: CountryCode,
+        province: String,
+    },
+    /// the phone number can't be turned into an E.164 number
+    InvalidPhone(String),
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownCountry(code) => write!(f, "unknown country code `{}`", code),
+            Self::InvalidPostalCode { country, zip } => {
+                write!(
+                    f,
+                    "`{}` is not a valid postal code in {}",
+                    zip,
+                    country.name()
+                )
+            }
+            Self::MissingProvince { country } => {
+                write!(
+                    f,
+                    "addresses in {} require a province or state",
+                    country.name()
+                )
+            }
+            Self::InvalidProvince { country, province } => {
+                write!(
+                    f,
+                    "`{}` is not a province or state of {}",
+                    province,
+                    country.name()
+                )
+            }
+            Self::InvalidPhone(phone) => write!(f, "`{}` is not a valid phone number", phone),
+        }
+    }
+}
+
+impl AddressError {
+    /// The address field the error is about, as named on the wire
+    pub fn field(&self) -> &'static str {
+        match self {
+            Self::UnknownCountry(_) => "country",
+            Self::InvalidPostalCode { .. } => "zip",
+            Self::MissingProvince { .. } | Self::InvalidProvince { .. } => "province",
+            Self::InvalidPhone(_) => "phone",
+        }
+    }
+}
+
+impl std::error::Error for AddressError {}
+
+/// CountryCode : An ISO 3166-1 alpha-2 country code, like `US` or `DE`.
+///
+/// Only assigned codes can be constructed. On the wire it is the bare
+/// two-letter string.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct CountryCode([u8; 2]);
+
+impl CountryCode {
+    pub const CA: CountryCode = CountryCode(*b"CA");
+    pub const DE: CountryCode = CountryCode(*b"DE");
+    pub const FR: CountryCode = CountryCode(*b"FR");
+    pub const GB: CountryCode = CountryCode(*b"GB");
+    pub const US: CountryCode = CountryCode(*b"US");
+
+    /// Parses a country code, ignoring case and surrounding whitespace
+    pub fn new(code: &str) -> Result<CountryCode, AddressError> {
+        let normalized = code.trim().to_ascii_uppercase();
+        COUNTRIES
+            .binary_search_by(|country| country.code.cmp(&normalized))
+            .map(|_| {
+                let bytes = normalized.as_bytes();
+                CountryCode([bytes[0], bytes[1]])
+            })
+            .map_err(|_| AddressError::UnknownCountry(code.to_string()))
+    }
+
+    /// Every assigned ISO 3166-1 alpha-2 code
+    pub fn all() -> impl Iterator<Item = CountryCode> {
+        COUNTRIES.iter().map(|country| {
+            let bytes = country.code.as_bytes();
+            CountryCode([bytes[0], bytes[1]])
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        // only ever built from ASCII codes in the table
+        std::str::from_utf8(&self.0).unwrap_or_default()
+    }
+
+    /// The country's English short name, e.g. `United Kingdom`
+    pub fn name(&self) -> &'static str {
+        self.data().name
+    }
+
+    /// The ITU calling code without the leading `+`, e.g. `44`.
+    /// `None` for uninhabited territories
+    pub fn calling_code(&self) -> Option<&'static str> {
+        self.data().calling_code
+    }
+
+    fn data(&self) -> &'static CountryData {
+        COUNTRIES
+            .iter()
+            .find(|country| country.code == self.as_str())
+            .expect("country codes are only built from the table")
+    }
+
+    /// The subdivisions an address in this country must name as its province,
+    /// as `(code, name)` pairs. `None` if the province is optional
+    pub fn required_provinces(&self) -> Option<&'static [(&'static str, &'static str)]> {
+        match self.as_str() {
+            "US" => Some(US_STATES),
+            "CA" => Some(CA_PROVINCES),
+            _ => None,
+        }
+    }
+
+    /// Checks a postal code against the country's formats and returns it in
+    /// its canonical form, e.g. `sw1a1aa` becomes `SW1A 1AA` in the UK.
+    /// Countries without a known format accept any non-empty code.
+    pub fn normalize_postal_code(&self, zip: &str) -> Result<String, AddressError> {
+        let invalid = || AddressError::InvalidPostalCode {
+            country: *self,
+            zip: zip.to_string(),
+        };
+
+        let compact: Vec<char> = zip
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        if compact.is_empty() {
+            return Err(invalid());
+        }
+
+        let Some((_, formats)) = POSTAL_FORMATS
+            .iter()
+            .find(|(code, _)| *code == self.as_str())
+        else {
+            return Ok(zip.trim().to_ascii_uppercase());
+        };
+
+        formats
+            .iter()
+            .find_map(|format| apply_postal_format(format, &compact))
+            .ok_or_else(invalid)
+    }
+
+    /// Checks that the province is given when required and is one of the
+    /// country's subdivisions, accepting either the code or the name.
+    /// Returns the subdivision code, or the trimmed input for countries that
+    /// don't require one.
+    pub fn normalize_province(
+        &self,
+        province: Option<&str>,
+    ) -> Result<Option<String>, AddressError> {
+        let province = province.map(str::trim).filter(|p| !p.is_empty());
+
+        let Some(subdivisions) = self.required_provinces() else {
+            return Ok(province.map(str::to_string));
+        };
+        let Some(province) = province else {
+            return Err(AddressError::MissingProvince { country: *self });
+        };
+
+        subdivisions
+            .iter()
+            .find(|(code, name)| {
+                code.eq_ignore_ascii_case(province) || name.eq_ignore_ascii_case(province)
+            })
+            .map(|(code, _)| Some(code.to_string()))
+            .ok_or_else(|| AddressError::InvalidProvince {
+                country: *self,
+                province: province.to_string(),
+            })
+    }
+
+    /// Normalizes a phone number to E.164 (`+` followed by up to 15 digits).
+    ///
+    /// Numbers starting with `+` or `00` are taken as international; anything
+    /// else is a national number in this country, so its trunk prefix (e.g.
+    /// the leading `0` in the UK) is dropped and the calling code added.
+    pub fn normalize_phone(&self, phone: &str) -> Result<String, AddressError> {
+        let invalid = || AddressError::InvalidPhone(phone.to_string());
+
+        let trimmed = phone.trim();
+        let is_formatting = |c: char| c.is_whitespace() || matches!(c, '-' | '.' | '(' | ')' | '/');
+        if !trimmed
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '+' || is_formatting(c))
+        {
+            return Err(invalid());
+        }
+
+        let digits: String = trimmed.chars().filter(|c| c.is_ascii_digit()).collect();
+        let international = if trimmed.starts_with('+') {
+            digits
+        } else if let Some(rest) = digits.strip_prefix("00") {
+            rest.to_string()
+        } else {
+            let calling_code = self.calling_code().ok_or_else(invalid)?;
+            let national = match self.data().trunk_prefix {
+                // NANP numbers are 10 digits, optionally written with the leading 1
+                Some(prefix) if calling_code == "1" => match digits.len() {
+                    11 => digits.strip_prefix(prefix).ok_or_else(invalid)?,
+                    10 => &digits,
+                    _ => return Err(invalid()),
+                },
+                Some(prefix) => digits.strip_prefix(prefix).unwrap_or(&digits),
+                None => &digits,
+            };
+            format!("{}{}", calling_code, national)
+        };
+
+        if !(8..=15).contains(&international.len()) || international.starts_with('0') {
+            return Err(invalid());
+        }
+
+        Ok(format!("+{}", international))
+    }
+}
+
+/// matches compact (whitespace-free) input against a postal format and
+/// returns it formatted, or `None` if it doesn't match
+fn apply_postal_format(format: &str, compact: &[char]) -> Option<String> {
+    let mut input = compact.iter();
+    let mut output = String::with_capacity(format.len());
+
+    for expected in format.chars() {
+        if expected == ' ' {
+            output.push(' ');
+            continue;
+        }
+
+        let c = *input.next()?;
+        let matches = match expected {
+            '#' => c.is_ascii_digit(),
+            '@' => c.is_ascii_alphabetic(),
+            '*' => c.is_ascii_alphanumeric(),
+            literal => c == literal,
+        };
+        if !matches {
+            return None;
+        }
+        output.push(c);
+    }
+
+    // all of the input must be consumed
+    input.next().is_none().then_some(output)
+}
+
+impl fmt::Display for CountryCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for CountryCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CountryCode({})", self.as_str())
+    }
+}
+
+impl Default for CountryCode {
+    fn default() -> CountryCode {
+        CountryCode::US
+    }
+}
+
+impl FromStr for CountryCode {
+    type Err = AddressError;
+
+    fn from_str(code: &str) -> Result<CountryCode, AddressError> {
+        CountryCode::new(code)
+    }
+}
+
+impl TryFrom<&str> for CountryCode {
+    type Error = AddressError;
+
+    fn try_from(code: &str) -> Result<CountryCode, AddressError> {
+        CountryCode::new(code)
+    }
+}
+
+impl PartialEq<&str> for CountryCode {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Serialize for CountryCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for CountryCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<CountryCode, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        CountryCode::new(&code).map_err(D::Error::custom)
+    }
+}
+
+/// Country : The country of an address sent by the server.
+///
+/// Normally one of the assigned codes in [`CountryCode`], but a code this
+/// client doesn't know about is kept as `Other` and serialized back unchanged
+/// instead of failing the whole response. Requests take a [`CountryCode`], so
+/// only assigned codes are ever sent.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Country {
+    Code(CountryCode),
+    /// A value this client doesn't know about, as sent by the server.
+    Other(String),
+}
+
+impl Country {
+    /// The assigned country code, or `None` for an unknown value
+    pub fn code(&self) -> Option<CountryCode> {
+        match self {
+            Self::Code(code) => Some(*code),
+            Self::Other(_) => None,
+        }
+    }
+
+    /// The value as sent over the wire
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Code(code) => code.as_str(),
+            Self::Other(value) => value,
+        }
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, Self::Other(_))
+    }
+}
+
+impl Default for Country {
+    fn default() -> Country {
+        Country::Code(CountryCode::default())
+    }
+}
+
+impl From<CountryCode> for Country {
+    fn from(code: CountryCode) -> Country {
+        Country::Code(code)
+    }
+}
+
+impl From<&str> for Country {
+    /// Takes an exact assigned code as [`Country::Code`] and anything else,
+    /// including differently written codes, as [`Country::Other`] so it
+    /// round-trips unchanged.
+    fn from(value: &str) -> Country {
+        match CountryCode::new(value) {
+            Ok(code) if code.as_str() == value => Country::Code(code),
+            _ => Country::Other(value.to_owned()),
+        }
+    }
+}
+
+impl From<String> for Country {
+    fn from(value: String) -> Country {
+        match Country::from(value.as_str()) {
+            Country::Other(_) => Country::Other(value),
+            code => code,
+        }
+    }
+}
+
+impl PartialEq<CountryCode> for Country {
+    fn eq(&self, other: &CountryCode) -> bool {
+        self.code() == Some(*other)
+    }
+}
+
+impl PartialEq<&str> for Country {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Display for Country {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Country {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Country {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Country, D::Error> {
+        String::deserialize(deserializer).map(Country::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_country_table_is_sorted() {
+        assert!(COUNTRIES.windows(2).all(|w| w[0].code < w[1].code));
+        assert_eq!(CountryCode::all().count(), 249);
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(CountryCode::new(" gb ").unwrap(), CountryCode::GB);
+        assert_eq!(CountryCode::GB.name(), "United Kingdom");
+        assert!(CountryCode::new("UK").is_err());
+        assert!(serde_json::from_str::<CountryCode>("\"ZZ\"").is_err());
+    }
+
+    #[test]
+    fn test_country_accepts_unknown_codes() {
+        let country: Country = serde_json::from_str("\"GB\"").unwrap();
+        assert_eq!(country, CountryCode::GB);
+
+        let country: Country = serde_json::from_str("\"ZZ\"").unwrap();
+        assert_eq!(country, Country::Other("ZZ".to_string()));
+        assert_eq!(country.code(), None);
+        assert_eq!(serde_json::to_string(&country).unwrap(), "\"ZZ\"");
+
+        // not canonical, so kept as sent
+        assert!(Country::from("gb").is_unknown());
+    }
+
+    #[test]
+    fn test_normalize_postal_code() {
+        let us = CountryCode::US;
+        assert_eq!(us.normalize_postal_code("12345").unwrap(), "12345");
+        assert_eq!(
+            us.normalize_postal_code("12345-6789").unwrap(),
+            "12345-6789"
+        );
+        assert!(us.normalize_postal_code("1234").is_err());
+
+        assert_eq!(
+            CountryCode::GB.normalize_postal_code("sw1a1aa").unwrap(),
+            "SW1A 1AA"
+        );
+        assert_eq!(
+            CountryCode::CA.normalize_postal_code("k1a 0b1").unwrap(),
+            "K1A 0B1"
+        );
+        assert!(CountryCode::CA.normalize_postal_code("K1A 0B").is_err());
+
+        // no known format, anything non-empty goes
+        let ae = CountryCode::new("AE").unwrap();
+        assert_eq!(ae.normalize_postal_code(" abc ").unwrap(), "ABC");
+        assert!(ae.normalize_postal_code("  ").is_err());
+    }
+
+    #[test]
+    fn test_normalize_province() {
+        let us = CountryCode::US;
+        assert_eq!(
+            us.normalize_province(Some("ny")).unwrap().as_deref(),
+            Some("NY")
+        );
+        assert_eq!(
+            us.normalize_province(Some("California"))
+                .unwrap()
+                .as_deref(),
+            Some("CA")
+        );
+        assert_eq!(
+            us.normalize_province(None),
+            Err(AddressError::MissingProvince { country: us })
+        );
+        assert!(us.normalize_province(Some("Ontario")).is_err());
+        assert_eq!(CountryCode::DE.normalize_province(None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_normalize_phone() {
+        let us = CountryCode::US;
+        assert_eq!(
+            us.normalize_phone("(555) 555-0123").unwrap(),
+            "+15555550123"
+        );
+        assert_eq!(
+            us.normalize_phone("1-555-555-0123").unwrap(),
+            "+15555550123"
+        );
+        assert!(us.normalize_phone("555-0123").is_err());
+
+        let gb = CountryCode::GB;
+        assert_eq!(
+            gb.normalize_phone("020 7946 0000").unwrap(),
+            "+442079460000"
+        );
+        assert_eq!(
+            gb.normalize_phone("+44 20 7946 0000").unwrap(),
+            "+442079460000"
+        );
+        assert_eq!(
+            gb.normalize_phone("0044 20 7946 0000").unwrap(),
+            "+442079460000"
+        );
+        assert!(gb.normalize_phone("call me").is_err());
+    }
+}
@@ -0,0 +1,353 @@
+/*
+ * Terminal API
+ *
+ * Hand-maintained: this file is listed in `.openapi-generator-ignore` and is
+ * not produced by OpenAPI Generator.
+ *
+ * Country names and codes are from ISO 3166-1 (via Debian's iso-codes), calling
+ * codes and national prefixes from libphonenumber's metadata.
+ */
+
+/// A row of the ISO 3166-1 table, sorted by code
+pub(super) struct CountryData {
+    pub code: &'static str,
+    pub name: &'static str,
+    /// ITU calling code, without the leading `+`. `None` for uninhabited territories
+    pub calling_code: Option<&'static str>,
+    /// prefix dialled before national numbers, e.g. the `0` in `020 7946 0000`
+    pub trunk_prefix: Option<&'static str>,
+}
+
+#[rustfmt::skip]
+pub(super) const COUNTRIES: &[CountryData] = &[
+    CountryData { code: "AD", name: "Andorra", calling_code: Some("376"), trunk_prefix: None },
+    CountryData { code: "AE", name: "United Arab Emirates", calling_code: Some("971"), trunk_prefix: Some("0") },
+    CountryData { code: "AF", name: "Afghanistan", calling_code: Some("93"), trunk_prefix: Some("0") },
+    CountryData { code: "AG", name: "Antigua and Barbuda", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "AI", name: "Anguilla", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "AL", name: "Albania", calling_code: Some("355"), trunk_prefix: Some("0") },
+    CountryData { code: "AM", name: "Armenia", calling_code: Some("374"), trunk_prefix: Some("0") },
+    CountryData { code: "AO", name: "Angola", calling_code: Some("244"), trunk_prefix: None },
+    CountryData { code: "AQ", name: "Antarctica", calling_code: None, trunk_prefix: None },
+    CountryData { code: "AR", name: "Argentina", calling_code: Some("54"), trunk_prefix: Some("0") },
+    CountryData { code: "AS", name: "American Samoa", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "AT", name: "Austria", calling_code: Some("43"), trunk_prefix: Some("0") },
+    CountryData { code: "AU", name: "Australia", calling_code: Some("61"), trunk_prefix: Some("0") },
+    CountryData { code: "AW", name: "Aruba", calling_code: Some("297"), trunk_prefix: None },
+    CountryData { code: "AX", name: "Åland Islands", calling_code: Some("358"), trunk_prefix: Some("0") },
+    CountryData { code: "AZ", name: "Azerbaijan", calling_code: Some("994"), trunk_prefix: Some("0") },
+    CountryData { code: "BA", name: "Bosnia and Herzegovina", calling_code: Some("387"), trunk_prefix: Some("0") },
+    CountryData { code: "BB", name: "Barbados", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "BD", name: "Bangladesh", calling_code: Some("880"), trunk_prefix: Some("0") },
+    CountryData { code: "BE", name: "Belgium", calling_code: Some("32"), trunk_prefix: Some("0") },
+    CountryData { code: "BF", name: "Burkina Faso", calling_code: Some("226"), trunk_prefix: None },
+    CountryData { code: "BG", name: "Bulgaria", calling_code: Some("359"), trunk_prefix: Some("0") },
+    CountryData { code: "BH", name: "Bahrain", calling_code: Some("973"), trunk_prefix: None },
+    CountryData { code: "BI", name: "Burundi", calling_code: Some("257"), trunk_prefix: None },
+    CountryData { code: "BJ", name: "Benin", calling_code: Some("229"), trunk_prefix: None },
+    CountryData { code: "BL", name: "Saint Barthélemy", calling_code: Some("590"), trunk_prefix: Some("0") },
+    CountryData { code: "BM", name: "Bermuda", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "BN", name: "Brunei Darussalam", calling_code: Some("673"), trunk_prefix: None },
+    CountryData { code: "BO", name: "Bolivia", calling_code: Some("591"), trunk_prefix: Some("0") },
+    CountryData { code: "BQ", name: "Bonaire, Sint Eustatius and Saba", calling_code: Some("599"), trunk_prefix: None },
+    CountryData { code: "BR", name: "Brazil", calling_code: Some("55"), trunk_prefix: Some("0") },
+    CountryData { code: "BS", name: "Bahamas", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "BT", name: "Bhutan", calling_code: Some("975"), trunk_prefix: None },
+    CountryData { code: "BV", name: "Bouvet Island", calling_code: None, trunk_prefix: None },
+    CountryData { code: "BW", name: "Botswana", calling_code: Some("267"), trunk_prefix: None },
+    CountryData { code: "BY", name: "Belarus", calling_code: Some("375"), trunk_prefix: Some("8") },
+    CountryData { code: "BZ", name: "Belize", calling_code: Some("501"), trunk_prefix: None },
+    CountryData { code: "CA", name: "Canada", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "CC", name: "Cocos (Keeling) Islands", calling_code: Some("61"), trunk_prefix: Some("0") },
+    CountryData { code: "CD", name: "Congo, The Democratic Republic of the", calling_code: Some("243"), trunk_prefix: Some("0") },
+    CountryData { code: "CF", name: "Central African Republic", calling_code: Some("236"), trunk_prefix: None },
+    CountryData { code: "CG", name: "Congo", calling_code: Some("242"), trunk_prefix: None },
+    CountryData { code: "CH", name: "Switzerland", calling_code: Some("41"), trunk_prefix: Some("0") },
+    CountryData { code: "CI", name: "Côte d'Ivoire", calling_code: Some("225"), trunk_prefix: None },
+    CountryData { code: "CK", name: "Cook Islands", calling_code: Some("682"), trunk_prefix: None },
+    CountryData { code: "CL", name: "Chile", calling_code: Some("56"), trunk_prefix: None },
+    CountryData { code: "CM", name: "Cameroon", calling_code: Some("237"), trunk_prefix: None },
+    CountryData { code: "CN", name: "China", calling_code: Some("86"), trunk_prefix: Some("0") },
+    CountryData { code: "CO", name: "Colombia", calling_code: Some("57"), trunk_prefix: Some("0") },
+    CountryData { code: "CR", name: "Costa Rica", calling_code: Some("506"), trunk_prefix: None },
+    CountryData { code: "CU", name: "Cuba", calling_code: Some("53"), trunk_prefix: Some("0") },
+    CountryData { code: "CV", name: "Cabo Verde", calling_code: Some("238"), trunk_prefix: None },
+    CountryData { code: "CW", name: "Curaçao", calling_code: Some("599"), trunk_prefix: None },
+    CountryData { code: "CX", name: "Christmas Island", calling_code: Some("61"), trunk_prefix: Some("0") },
+    CountryData { code: "CY", name: "Cyprus", calling_code: Some("357"), trunk_prefix: None },
+    CountryData { code: "CZ", name: "Czechia", calling_code: Some("420"), trunk_prefix: None },
+    CountryData { code: "DE", name: "Germany", calling_code: Some("49"), trunk_prefix: Some("0") },
+    CountryData { code: "DJ", name: "Djibouti", calling_code: Some("253"), trunk_prefix: None },
+    CountryData { code: "DK", name: "Denmark", calling_code: Some("45"), trunk_prefix: None },
+    CountryData { code: "DM", name: "Dominica", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "DO", name: "Dominican Republic", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "DZ", name: "Algeria", calling_code: Some("213"), trunk_prefix: Some("0") },
+    CountryData { code: "EC", name: "Ecuador", calling_code: Some("593"), trunk_prefix: Some("0") },
+    CountryData { code: "EE", name: "Estonia", calling_code: Some("372"), trunk_prefix: None },
+    CountryData { code: "EG", name: "Egypt", calling_code: Some("20"), trunk_prefix: Some("0") },
+    CountryData { code: "EH", name: "Western Sahara", calling_code: Some("212"), trunk_prefix: Some("0") },
+    CountryData { code: "ER", name: "Eritrea", calling_code: Some("291"), trunk_prefix: Some("0") },
+    CountryData { code: "ES", name: "Spain", calling_code: Some("34"), trunk_prefix: None },
+    CountryData { code: "ET", name: "Ethiopia", calling_code: Some("251"), trunk_prefix: Some("0") },
+    CountryData { code: "FI", name: "Finland", calling_code: Some("358"), trunk_prefix: Some("0") },
+    CountryData { code: "FJ", name: "Fiji", calling_code: Some("679"), trunk_prefix: None },
+    CountryData { code: "FK", name: "Falkland Islands (Malvinas)", calling_code: Some("500"), trunk_prefix: None },
+    CountryData { code: "FM", name: "Micronesia, Federated States of", calling_code: Some("691"), trunk_prefix: None },
+    CountryData { code: "FO", name: "Faroe Islands", calling_code: Some("298"), trunk_prefix: None },
+    CountryData { code: "FR", name: "France", calling_code: Some("33"), trunk_prefix: Some("0") },
+    CountryData { code: "GA", name: "Gabon", calling_code: Some("241"), trunk_prefix: None },
+    CountryData { code: "GB", name: "United Kingdom", calling_code: Some("44"), trunk_prefix: Some("0") },
+    CountryData { code: "GD", name: "Grenada", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "GE", name: "Georgia", calling_code: Some("995"), trunk_prefix: Some("0") },
+    CountryData { code: "GF", name: "French Guiana", calling_code: Some("594"), trunk_prefix: Some("0") },
+    CountryData { code: "GG", name: "Guernsey", calling_code: Some("44"), trunk_prefix: Some("0") },
+    CountryData { code: "GH", name: "Ghana", calling_code: Some("233"), trunk_prefix: Some("0") },
+    CountryData { code: "GI", name: "Gibraltar", calling_code: Some("350"), trunk_prefix: None },
+    CountryData { code: "GL", name: "Greenland", calling_code: Some("299"), trunk_prefix: None },
+    CountryData { code: "GM", name: "Gambia", calling_code: Some("220"), trunk_prefix: None },
+    CountryData { code: "GN", name: "Guinea", calling_code: Some("224"), trunk_prefix: None },
+    CountryData { code: "GP", name: "Guadeloupe", calling_code: Some("590"), trunk_prefix: Some("0") },
+    CountryData { code: "GQ", name: "Equatorial Guinea", calling_code: Some("240"), trunk_prefix: None },
+    CountryData { code: "GR", name: "Greece", calling_code: Some("30"), trunk_prefix: None },
+    CountryData { code: "GS", name: "South Georgia and the South Sandwich Islands", calling_code: None, trunk_prefix: None },
+    CountryData { code: "GT", name: "Guatemala", calling_code: Some("502"), trunk_prefix: None },
+    CountryData { code: "GU", name: "Guam", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "GW", name: "Guinea-Bissau", calling_code: Some("245"), trunk_prefix: None },
+    CountryData { code: "GY", name: "Guyana", calling_code: Some("592"), trunk_prefix: None },
+    CountryData { code: "HK", name: "Hong Kong", calling_code: Some("852"), trunk_prefix: None },
+    CountryData { code: "HM", name: "Heard Island and McDonald Islands", calling_code: None, trunk_prefix: None },
+    CountryData { code: "HN", name: "Honduras", calling_code: Some("504"), trunk_prefix: None },
+    CountryData { code: "HR", name: "Croatia", calling_code: Some("385"), trunk_prefix: Some("0") },
+    CountryData { code: "HT", name: "Haiti", calling_code: Some("509"), trunk_prefix: None },
+    CountryData { code: "HU", name: "Hungary", calling_code: Some("36"), trunk_prefix: Some("06") },
+    CountryData { code: "ID", name: "Indonesia", calling_code: Some("62"), trunk_prefix: Some("0") },
+    CountryData { code: "IE", name: "Ireland", calling_code: Some("353"), trunk_prefix: Some("0") },
+    CountryData { code: "IL", name: "Israel", calling_code: Some("972"), trunk_prefix: Some("0") },
+    CountryData { code: "IM", name: "Isle of Man", calling_code: Some("44"), trunk_prefix: Some("0") },
+    CountryData { code: "IN", name: "India", calling_code: Some("91"), trunk_prefix: Some("0") },
+    CountryData { code: "IO", name: "British Indian Ocean Territory", calling_code: Some("246"), trunk_prefix: None },
+    CountryData { code: "IQ", name: "Iraq", calling_code: Some("964"), trunk_prefix: Some("0") },
+    CountryData { code: "IR", name: "Iran", calling_code: Some("98"), trunk_prefix: Some("0") },
+    CountryData { code: "IS", name: "Iceland", calling_code: Some("354"), trunk_prefix: None },
+    CountryData { code: "IT", name: "Italy", calling_code: Some("39"), trunk_prefix: None },
+    CountryData { code: "JE", name: "Jersey", calling_code: Some("44"), trunk_prefix: Some("0") },
+    CountryData { code: "JM", name: "Jamaica", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "JO", name: "Jordan", calling_code: Some("962"), trunk_prefix: Some("0") },
+    CountryData { code: "JP", name: "Japan", calling_code: Some("81"), trunk_prefix: Some("0") },
+    CountryData { code: "KE", name: "Kenya", calling_code: Some("254"), trunk_prefix: Some("0") },
+    CountryData { code: "KG", name: "Kyrgyzstan", calling_code: Some("996"), trunk_prefix: Some("0") },
+    CountryData { code: "KH", name: "Cambodia", calling_code: Some("855"), trunk_prefix: Some("0") },
+    CountryData { code: "KI", name: "Kiribati", calling_code: Some("686"), trunk_prefix: Some("0") },
+    CountryData { code: "KM", name: "Comoros", calling_code: Some("269"), trunk_prefix: None },
+    CountryData { code: "KN", name: "Saint Kitts and Nevis", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "KP", name: "North Korea", calling_code: Some("850"), trunk_prefix: Some("0") },
+    CountryData { code: "KR", name: "South Korea", calling_code: Some("82"), trunk_prefix: Some("0") },
+    CountryData { code: "KW", name: "Kuwait", calling_code: Some("965"), trunk_prefix: None },
+    CountryData { code: "KY", name: "Cayman Islands", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "KZ", name: "Kazakhstan", calling_code: Some("7"), trunk_prefix: Some("8") },
+    CountryData { code: "LA", name: "Laos", calling_code: Some("856"), trunk_prefix: Some("0") },
+    CountryData { code: "LB", name: "Lebanon", calling_code: Some("961"), trunk_prefix: Some("0") },
+    CountryData { code: "LC", name: "Saint Lucia", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "LI", name: "Liechtenstein", calling_code: Some("423"), trunk_prefix: Some("0") },
+    CountryData { code: "LK", name: "Sri Lanka", calling_code: Some("94"), trunk_prefix: Some("0") },
+    CountryData { code: "LR", name: "Liberia", calling_code: Some("231"), trunk_prefix: Some("0") },
+    CountryData { code: "LS", name: "Lesotho", calling_code: Some("266"), trunk_prefix: None },
+    CountryData { code: "LT", name: "Lithuania", calling_code: Some("370"), trunk_prefix: Some("0") },
+    CountryData { code: "LU", name: "Luxembourg", calling_code: Some("352"), trunk_prefix: None },
+    CountryData { code: "LV", name: "Latvia", calling_code: Some("371"), trunk_prefix: None },
+    CountryData { code: "LY", name: "Libya", calling_code: Some("218"), trunk_prefix: Some("0") },
+    CountryData { code: "MA", name: "Morocco", calling_code: Some("212"), trunk_prefix: Some("0") },
+    CountryData { code: "MC", name: "Monaco", calling_code: Some("377"), trunk_prefix: Some("0") },
+    CountryData { code: "MD", name: "Moldova", calling_code: Some("373"), trunk_prefix: Some("0") },
+    CountryData { code: "ME", name: "Montenegro", calling_code: Some("382"), trunk_prefix: Some("0") },
+    CountryData { code: "MF", name: "Saint Martin (French part)", calling_code: Some("590"), trunk_prefix: Some("0") },
+    CountryData { code: "MG", name: "Madagascar", calling_code: Some("261"), trunk_prefix: Some("0") },
+    CountryData { code: "MH", name: "Marshall Islands", calling_code: Some("692"), trunk_prefix: Some("1") },
+    CountryData { code: "MK", name: "North Macedonia", calling_code: Some("389"), trunk_prefix: Some("0") },
+    CountryData { code: "ML", name: "Mali", calling_code: Some("223"), trunk_prefix: None },
+    CountryData { code: "MM", name: "Myanmar", calling_code: Some("95"), trunk_prefix: Some("0") },
+    CountryData { code: "MN", name: "Mongolia", calling_code: Some("976"), trunk_prefix: Some("0") },
+    CountryData { code: "MO", name: "Macao", calling_code: Some("853"), trunk_prefix: None },
+    CountryData { code: "MP", name: "Northern Mariana Islands", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "MQ", name: "Martinique", calling_code: Some("596"), trunk_prefix: Some("0") },
+    CountryData { code: "MR", name: "Mauritania", calling_code: Some("222"), trunk_prefix: None },
+    CountryData { code: "MS", name: "Montserrat", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "MT", name: "Malta", calling_code: Some("356"), trunk_prefix: None },
+    CountryData { code: "MU", name: "Mauritius", calling_code: Some("230"), trunk_prefix: None },
+    CountryData { code: "MV", name: "Maldives", calling_code: Some("960"), trunk_prefix: None },
+    CountryData { code: "MW", name: "Malawi", calling_code: Some("265"), trunk_prefix: Some("0") },
+    CountryData { code: "MX", name: "Mexico", calling_code: Some("52"), trunk_prefix: None },
+    CountryData { code: "MY", name: "Malaysia", calling_code: Some("60"), trunk_prefix: Some("0") },
+    CountryData { code: "MZ", name: "Mozambique", calling_code: Some("258"), trunk_prefix: None },
+    CountryData { code: "NA", name: "Namibia", calling_code: Some("264"), trunk_prefix: Some("0") },
+    CountryData { code: "NC", name: "New Caledonia", calling_code: Some("687"), trunk_prefix: None },
+    CountryData { code: "NE", name: "Niger", calling_code: Some("227"), trunk_prefix: None },
+    CountryData { code: "NF", name: "Norfolk Island", calling_code: Some("672"), trunk_prefix: None },
+    CountryData { code: "NG", name: "Nigeria", calling_code: Some("234"), trunk_prefix: Some("0") },
+    CountryData { code: "NI", name: "Nicaragua", calling_code: Some("505"), trunk_prefix: None },
+    CountryData { code: "NL", name: "Netherlands", calling_code: Some("31"), trunk_prefix: Some("0") },
+    CountryData { code: "NO", name: "Norway", calling_code: Some("47"), trunk_prefix: None },
+    CountryData { code: "NP", name: "Nepal", calling_code: Some("977"), trunk_prefix: Some("0") },
+    CountryData { code: "NR", name: "Nauru", calling_code: Some("674"), trunk_prefix: None },
+    CountryData { code: "NU", name: "Niue", calling_code: Some("683"), trunk_prefix: None },
+    CountryData { code: "NZ", name: "New Zealand", calling_code: Some("64"), trunk_prefix: Some("0") },
+    CountryData { code: "OM", name: "Oman", calling_code: Some("968"), trunk_prefix: None },
+    CountryData { code: "PA", name: "Panama", calling_code: Some("507"), trunk_prefix: None },
+    CountryData { code: "PE", name: "Peru", calling_code: Some("51"), trunk_prefix: Some("0") },
+    CountryData { code: "PF", name: "French Polynesia", calling_code: Some("689"), trunk_prefix: None },
+    CountryData { code: "PG", name: "Papua New Guinea", calling_code: Some("675"), trunk_prefix: None },
+    CountryData { code: "PH", name: "Philippines", calling_code: Some("63"), trunk_prefix: Some("0") },
+    CountryData { code: "PK", name: "Pakistan", calling_code: Some("92"), trunk_prefix: Some("0") },
+    CountryData { code: "PL", name: "Poland", calling_code: Some("48"), trunk_prefix: None },
+    CountryData { code: "PM", name: "Saint Pierre and Miquelon", calling_code: Some("508"), trunk_prefix: Some("0") },
+    CountryData { code: "PN", name: "Pitcairn", calling_code: None, trunk_prefix: None },
+    CountryData { code: "PR", name: "Puerto Rico", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "PS", name: "Palestine, State of", calling_code: Some("970"), trunk_prefix: Some("0") },
+    CountryData { code: "PT", name: "Portugal", calling_code: Some("351"), trunk_prefix: None },
+    CountryData { code: "PW", name: "Palau", calling_code: Some("680"), trunk_prefix: None },
+    CountryData { code: "PY", name: "Paraguay", calling_code: Some("595"), trunk_prefix: Some("0") },
+    CountryData { code: "QA", name: "Qatar", calling_code: Some("974"), trunk_prefix: None },
+    CountryData { code: "RE", name: "Réunion", calling_code: Some("262"), trunk_prefix: Some("0") },
+    CountryData { code: "RO", name: "Romania", calling_code: Some("40"), trunk_prefix: Some("0") },
+    CountryData { code: "RS", name: "Serbia", calling_code: Some("381"), trunk_prefix: Some("0") },
+    CountryData { code: "RU", name: "Russian Federation", calling_code: Some("7"), trunk_prefix: Some("8") },
+    CountryData { code: "RW", name: "Rwanda", calling_code: Some("250"), trunk_prefix: Some("0") },
+    CountryData { code: "SA", name: "Saudi Arabia", calling_code: Some("966"), trunk_prefix: Some("0") },
+    CountryData { code: "SB", name: "Solomon Islands", calling_code: Some("677"), trunk_prefix: None },
+    CountryData { code: "SC", name: "Seychelles", calling_code: Some("248"), trunk_prefix: None },
+    CountryData { code: "SD", name: "Sudan", calling_code: Some("249"), trunk_prefix: Some("0") },
+    CountryData { code: "SE", name: "Sweden", calling_code: Some("46"), trunk_prefix: Some("0") },
+    CountryData { code: "SG", name: "Singapore", calling_code: Some("65"), trunk_prefix: None },
+    CountryData { code: "SH", name: "Saint Helena, Ascension and Tristan da Cunha", calling_code: Some("290"), trunk_prefix: None },
+    CountryData { code: "SI", name: "Slovenia", calling_code: Some("386"), trunk_prefix: Some("0") },
+    CountryData { code: "SJ", name: "Svalbard and Jan Mayen", calling_code: Some("47"), trunk_prefix: None },
+    CountryData { code: "SK", name: "Slovakia", calling_code: Some("421"), trunk_prefix: Some("0") },
+    CountryData { code: "SL", name: "Sierra Leone", calling_code: Some("232"), trunk_prefix: Some("0") },
+    CountryData { code: "SM", name: "San Marino", calling_code: Some("378"), trunk_prefix: None },
+    CountryData { code: "SN", name: "Senegal", calling_code: Some("221"), trunk_prefix: None },
+    CountryData { code: "SO", name: "Somalia", calling_code: Some("252"), trunk_prefix: Some("0") },
+    CountryData { code: "SR", name: "Suriname", calling_code: Some("597"), trunk_prefix: None },
+    CountryData { code: "SS", name: "South Sudan", calling_code: Some("211"), trunk_prefix: Some("0") },
+    CountryData { code: "ST", name: "Sao Tome and Principe", calling_code: Some("239"), trunk_prefix: None },
+    CountryData { code: "SV", name: "El Salvador", calling_code: Some("503"), trunk_prefix: None },
+    CountryData { code: "SX", name: "Sint Maarten (Dutch part)", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "SY", name: "Syria", calling_code: Some("963"), trunk_prefix: Some("0") },
+    CountryData { code: "SZ", name: "Eswatini", calling_code: Some("268"), trunk_prefix: None },
+    CountryData { code: "TC", name: "Turks and Caicos Islands", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "TD", name: "Chad", calling_code: Some("235"), trunk_prefix: None },
+    CountryData { code: "TF", name: "French Southern Territories", calling_code: None, trunk_prefix: None },
+    CountryData { code: "TG", name: "Togo", calling_code: Some("228"), trunk_prefix: None },
+    CountryData { code: "TH", name: "Thailand", calling_code: Some("66"), trunk_prefix: Some("0") },
+    CountryData { code: "TJ", name: "Tajikistan", calling_code: Some("992"), trunk_prefix: None },
+    CountryData { code: "TK", name: "Tokelau", calling_code: Some("690"), trunk_prefix: None },
+    CountryData { code: "TL", name: "Timor-Leste", calling_code: Some("670"), trunk_prefix: None },
+    CountryData { code: "TM", name: "Turkmenistan", calling_code: Some("993"), trunk_prefix: Some("8") },
+    CountryData { code: "TN", name: "Tunisia", calling_code: Some("216"), trunk_prefix: None },
+    CountryData { code: "TO", name: "Tonga", calling_code: Some("676"), trunk_prefix: None },
+    CountryData { code: "TR", name: "Türkiye", calling_code: Some("90"), trunk_prefix: Some("0") },
+    CountryData { code: "TT", name: "Trinidad and Tobago", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "TV", name: "Tuvalu", calling_code: Some("688"), trunk_prefix: None },
+    CountryData { code: "TW", name: "Taiwan", calling_code: Some("886"), trunk_prefix: Some("0") },
+    CountryData { code: "TZ", name: "Tanzania", calling_code: Some("255"), trunk_prefix: Some("0") },
+    CountryData { code: "UA", name: "Ukraine", calling_code: Some("380"), trunk_prefix: Some("0") },
+    CountryData { code: "UG", name: "Uganda", calling_code: Some("256"), trunk_prefix: Some("0") },
+    CountryData { code: "UM", name: "United States Minor Outlying Islands", calling_code: None, trunk_prefix: None },
+    CountryData { code: "US", name: "United States", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "UY", name: "Uruguay", calling_code: Some("598"), trunk_prefix: Some("0") },
+    CountryData { code: "UZ", name: "Uzbekistan", calling_code: Some("998"), trunk_prefix: None },
+    CountryData { code: "VA", name: "Holy See (Vatican City State)", calling_code: Some("39"), trunk_prefix: None },
+    CountryData { code: "VC", name: "Saint Vincent and the Grenadines", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "VE", name: "Venezuela", calling_code: Some("58"), trunk_prefix: Some("0") },
+    CountryData { code: "VG", name: "Virgin Islands, British", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "VI", name: "Virgin Islands, U.S.", calling_code: Some("1"), trunk_prefix: Some("1") },
+    CountryData { code: "VN", name: "Vietnam", calling_code: Some("84"), trunk_prefix: Some("0") },
+    CountryData { code: "VU", name: "Vanuatu", calling_code: Some("678"), trunk_prefix: None },
+    CountryData { code: "WF", name: "Wallis and Futuna", calling_code: Some("681"), trunk_prefix: None },
+    CountryData { code: "WS", name: "Samoa", calling_code: Some("685"), trunk_prefix: None },
+    CountryData { code: "YE", name: "Yemen", calling_code: Some("967"), trunk_prefix: Some("0") },
+    CountryData { code: "YT", name: "Mayotte", calling_code: Some("262"), trunk_prefix: Some("0") },
+    CountryData { code: "ZA", name: "South Africa", calling_code: Some("27"), trunk_prefix: Some("0") },
+    CountryData { code: "ZM", name: "Zambia", calling_code: Some("260"), trunk_prefix: Some("0") },
+    CountryData { code: "ZW", name: "Zimbabwe", calling_code: Some("263"), trunk_prefix: Some("0") },
+];
+
+/// US states, districts and territories, plus the USPS armed forces codes
+pub(super) const US_STATES: &[(&str, &str)] = &[
+    ("AA", "Armed Forces Americas"),
+    ("AE", "Armed Forces Europe"),
+    ("AK", "Alaska"),
+    ("AL", "Alabama"),
+    ("AP", "Armed Forces Pacific"),
+    ("AR", "Arkansas"),
+    ("AS", "American Samoa"),
+    ("AZ", "Arizona"),
+    ("CA", "California"),
+    ("CO", "Colorado"),
+    ("CT", "Connecticut"),
+    ("DC", "District of Columbia"),
+    ("DE", "Delaware"),
+    ("FL", "Florida"),
+    ("GA", "Georgia"),
+    ("GU", "Guam"),
+    ("HI", "Hawaii"),
+    ("IA", "Iowa"),
+    ("ID", "Idaho"),
+    ("IL", "Illinois"),
+    ("IN", "Indiana"),
+    ("KS", "Kansas"),
+    ("KY", "Kentucky"),
+    ("LA", "Louisiana"),
+    ("MA", "Massachusetts"),
+    ("MD", "Maryland"),
+    ("ME", "Maine"),
+    ("MI", "Michigan"),
+    ("MN", "Minnesota"),
+    ("MO", "Missouri"),
+    ("MP", "Northern Mariana Islands"),
+    ("MS", "Mississippi"),
+    ("MT", "Montana"),
+    ("NC", "North Carolina"),
+    ("ND", "North Dakota"),
+    ("NE", "Nebraska"),
+    ("NH", "New Hampshire"),
+    ("NJ", "New Jersey"),
+    ("NM", "New Mexico"),
+    ("NV", "Nevada"),
+    ("NY", "New York"),
+    ("OH", "Ohio"),
+    ("OK", "Oklahoma"),
+    ("OR", "Oregon"),
+    ("PA", "Pennsylvania"),
+    ("PR", "Puerto Rico"),
+    ("RI", "Rhode Island"),
+    ("SC", "South Carolina"),
+    ("SD", "South Dakota"),
+    ("TN", "Tennessee"),
+    ("TX", "Texas"),
+    ("UM", "United States Minor Outlying Islands"),
+    ("UT", "Utah"),
+    ("VA", "Virginia"),
+    ("VI", "Virgin Islands, U.S."),
+    ("VT", "Vermont"),
+    ("WA", "Washington"),
+    ("WI", "Wisconsin"),
+    ("WV", "West Virginia"),
+    ("WY", "Wyoming"),
+];
+
+/// Canadian provinces and territories
+pub(super) const CA_PROVINCES: &[(&str, &str)] = &[
+    ("AB", "Alberta"),
+    ("BC", "British Columbia"),
+    ("MB", "Manitoba"),
+    ("NB", "New Brunswick"),
+    ("NL", "Newfoundland and Labrador"),
+    ("NS", "Nova Scotia"),
+    ("NT", "Northwest Territories"),
+    ("NU", "Nunavut"),
+    ("ON", "Ontario"),
+    ("PE", "Prince Edward Island"),
+    ("QC", "Quebec"),
+    ("SK", "Saskatchewan"),
+    ("YT", "Yukon"),
+];
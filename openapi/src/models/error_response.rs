@@ -40,21 +40,16 @@ impl ErrorResponse {
         }
     }
 }
-/// The error type category
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
-pub enum Type {
-    #[serde(rename = "validation")]
-    Validation,
-    #[serde(rename = "authentication")]
-    Authentication,
-    #[serde(rename = "forbidden")]
-    Forbidden,
-    #[serde(rename = "not_found")]
-    NotFound,
-    #[serde(rename = "rate_limit")]
-    RateLimit,
-    #[serde(rename = "internal")]
-    Internal,
+open_enum! {
+    /// The error type category
+    pub enum Type {
+        Validation => "validation",
+        Authentication => "authentication",
+        Forbidden => "forbidden",
+        NotFound => "not_found",
+        RateLimit => "rate_limit",
+        Internal => "internal",
+    }
 }
 
 impl Default for Type {
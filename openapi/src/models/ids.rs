@@ -0,0 +1,147 @@
+/*
+ * Terminal API
+ *
+ * Hand-maintained: this file is listed in `.openapi-generator-ignore` and is
+ * not produced by OpenAPI Generator.
+ */
+
+use std::borrow::Borrow;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Defines a string-backed identifier newtype that serializes transparently,
+/// so it has the same wire format as the `String` it replaces.
+macro_rules! id_type {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Default, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn new(id: impl Into<String>) -> $name {
+                $name(id.into())
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(id: String) -> $name {
+                $name(id)
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(id: &str) -> $name {
+                $name(id.to_owned())
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> String {
+                id.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        // lets client methods take `impl AsRef<CardId>`, so both owned and borrowed IDs work
+        impl AsRef<$name> for $name {
+            fn as_ref(&self) -> &$name {
+                self
+            }
+        }
+
+        impl Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+
+id_type!(
+    /// AddressId : ID of a shipping address.
+    AddressId
+);
+id_type!(
+    /// AppId : OAuth 2.0 client ID of an app.
+    AppId
+);
+id_type!(
+    /// CardId : ID of a credit card.
+    CardId
+);
+id_type!(
+    /// OrderId : ID of an order.
+    OrderId
+);
+id_type!(
+    /// ProductId : ID of a product.
+    ProductId
+);
+id_type!(
+    /// SubscriptionId : ID of a subscription.
+    SubscriptionId
+);
+id_type!(
+    /// TokenId : ID of a personal access token.
+    TokenId
+);
+id_type!(
+    /// VariantId : ID of a product variant.
+    VariantId
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_serializes_transparently() {
+        let id: CardId = serde_json::from_str("\"crd_XXXXXXXXXXXXXXXXXXXXXXXXX\"").unwrap();
+        assert_eq!(id, "crd_XXXXXXXXXXXXXXXXXXXXXXXXX");
+        assert_eq!(
+            serde_json::to_string(&id).unwrap(),
+            "\"crd_XXXXXXXXXXXXXXXXXXXXXXXXX\""
+        );
+    }
+
+    #[test]
+    fn test_map_keys() {
+        let variants: HashMap<VariantId, i32> =
+            serde_json::from_str(r#"{"var_XXXXXXXXXXXXXXXXXXXXXXXXX":2}"#).unwrap();
+        assert_eq!(variants.get("var_XXXXXXXXXXXXXXXXXXXXXXXXX"), Some(&2));
+    }
+}
@@ -1,130 +1,285 @@
+#[macro_use]
+mod open_enum;
+
+#[cfg(any(feature = "address", feature = "miscellaneous"))]
 pub mod address;
+#[cfg(any(feature = "address", feature = "miscellaneous"))]
 pub use self::address::Address;
+#[cfg(any(feature = "app", feature = "miscellaneous"))]
 pub mod app;
+#[cfg(any(feature = "app", feature = "miscellaneous"))]
 pub use self::app::App;
+#[cfg(any(feature = "card", feature = "miscellaneous"))]
 pub mod card;
+#[cfg(any(feature = "card", feature = "miscellaneous"))]
 pub use self::card::Card;
+#[cfg(any(feature = "card", feature = "miscellaneous"))]
+pub mod card_brand;
+#[cfg(any(feature = "card", feature = "miscellaneous"))]
+pub use self::card_brand::CardBrand;
+#[cfg(any(feature = "card", feature = "miscellaneous"))]
 pub mod card_expiration;
+#[cfg(any(feature = "card", feature = "miscellaneous"))]
 pub use self::card_expiration::CardExpiration;
+#[cfg(any(feature = "cart", feature = "miscellaneous"))]
 pub mod cart;
+#[cfg(any(feature = "cart", feature = "miscellaneous"))]
 pub use self::cart::Cart;
+#[cfg(any(feature = "cart", feature = "miscellaneous"))]
 pub mod cart_amount;
+#[cfg(any(feature = "cart", feature = "miscellaneous"))]
 pub use self::cart_amount::CartAmount;
+#[cfg(any(feature = "cart", feature = "miscellaneous"))]
 pub mod cart_item;
+#[cfg(any(feature = "cart", feature = "miscellaneous"))]
 pub use self::cart_item::CartItem;
+#[cfg(any(feature = "cart", feature = "miscellaneous"))]
 pub mod cart_shipping;
+#[cfg(any(feature = "cart", feature = "miscellaneous"))]
 pub use self::cart_shipping::CartShipping;
+#[cfg(any(feature = "address", feature = "cart", feature = "miscellaneous", feature = "order"))]
+pub mod country_code;
+#[cfg(any(feature = "address", feature = "cart", feature = "miscellaneous", feature = "order"))]
+pub use self::country_code::{AddressError, Country, CountryCode};
+#[cfg(any(feature = "address", feature = "cart", feature = "miscellaneous", feature = "order"))]
+mod country_data;
+#[cfg(any(feature = "address", feature = "app", feature = "card", feature = "cart", feature = "miscellaneous", feature = "subscription", feature = "token"))]
 pub mod delete_address_by_id_200_response;
+#[cfg(any(feature = "address", feature = "app", feature = "card", feature = "cart", feature = "miscellaneous", feature = "subscription", feature = "token"))]
 pub use self::delete_address_by_id_200_response::DeleteAddressById200Response;
 pub mod error_response;
 pub use self::error_response::ErrorResponse;
+#[cfg(any(feature = "miscellaneous", feature = "product", feature = "subscription"))]
 pub mod fixed;
+#[cfg(any(feature = "miscellaneous", feature = "product", feature = "subscription"))]
 pub use self::fixed::Fixed;
+#[cfg(feature = "address")]
 pub mod get_address_200_response;
+#[cfg(feature = "address")]
 pub use self::get_address_200_response::GetAddress200Response;
+#[cfg(feature = "address")]
 pub mod get_address_by_id_200_response;
+#[cfg(feature = "address")]
 pub use self::get_address_by_id_200_response::GetAddressById200Response;
+#[cfg(feature = "app")]
 pub mod get_app_200_response;
+#[cfg(feature = "app")]
 pub use self::get_app_200_response::GetApp200Response;
+#[cfg(feature = "app")]
 pub mod get_app_by_id_200_response;
+#[cfg(feature = "app")]
 pub use self::get_app_by_id_200_response::GetAppById200Response;
+#[cfg(feature = "card")]
 pub mod get_card_200_response;
+#[cfg(feature = "card")]
 pub use self::get_card_200_response::GetCard200Response;
+#[cfg(feature = "card")]
 pub mod get_card_by_id_200_response;
+#[cfg(feature = "card")]
 pub use self::get_card_by_id_200_response::GetCardById200Response;
+#[cfg(feature = "cart")]
 pub mod get_cart_200_response;
+#[cfg(feature = "cart")]
 pub use self::get_cart_200_response::GetCart200Response;
+#[cfg(feature = "order")]
 pub mod get_order_200_response;
+#[cfg(feature = "order")]
 pub use self::get_order_200_response::GetOrder200Response;
+#[cfg(feature = "order")]
 pub mod get_order_by_id_200_response;
+#[cfg(feature = "order")]
 pub use self::get_order_by_id_200_response::GetOrderById200Response;
+#[cfg(feature = "product")]
 pub mod get_product_200_response;
+#[cfg(feature = "product")]
 pub use self::get_product_200_response::GetProduct200Response;
+#[cfg(feature = "product")]
 pub mod get_product_by_id_200_response;
+#[cfg(feature = "product")]
 pub use self::get_product_by_id_200_response::GetProductById200Response;
+#[cfg(feature = "profile")]
 pub mod get_profile_200_response;
+#[cfg(feature = "profile")]
 pub use self::get_profile_200_response::GetProfile200Response;
+#[cfg(feature = "subscription")]
 pub mod get_subscription_200_response;
+#[cfg(feature = "subscription")]
 pub use self::get_subscription_200_response::GetSubscription200Response;
+#[cfg(feature = "subscription")]
 pub mod get_subscription_by_id_200_response;
+#[cfg(feature = "subscription")]
 pub use self::get_subscription_by_id_200_response::GetSubscriptionById200Response;
+#[cfg(feature = "token")]
 pub mod get_token_200_response;
+#[cfg(feature = "token")]
 pub use self::get_token_200_response::GetToken200Response;
+#[cfg(feature = "token")]
 pub mod get_token_by_id_200_response;
+#[cfg(feature = "token")]
 pub use self::get_token_by_id_200_response::GetTokenById200Response;
+#[cfg(feature = "miscellaneous")]
 pub mod get_view_init_200_response;
+#[cfg(feature = "miscellaneous")]
 pub use self::get_view_init_200_response::GetViewInit200Response;
+#[cfg(feature = "miscellaneous")]
 pub mod get_view_init_200_response_data;
+#[cfg(feature = "miscellaneous")]
 pub use self::get_view_init_200_response_data::GetViewInit200ResponseData;
+#[cfg(any(feature = "address", feature = "app", feature = "card", feature = "cart", feature = "miscellaneous", feature = "order", feature = "product", feature = "subscription", feature = "token"))]
+pub mod ids;
+#[cfg(any(feature = "address", feature = "app", feature = "card", feature = "cart", feature = "miscellaneous", feature = "order", feature = "product", feature = "subscription", feature = "token"))]
+pub use self::ids::{AddressId, AppId, CardId, OrderId, ProductId, SubscriptionId, TokenId, VariantId};
+#[cfg(any(feature = "cart", feature = "miscellaneous", feature = "order", feature = "product"))]
+pub mod money;
+#[cfg(any(feature = "cart", feature = "miscellaneous", feature = "order", feature = "product"))]
+pub use self::money::{Currency, Locale, Money};
+#[cfg(any(feature = "cart", feature = "miscellaneous", feature = "order"))]
 pub mod order;
+#[cfg(any(feature = "cart", feature = "miscellaneous", feature = "order"))]
 pub use self::order::Order;
+#[cfg(any(feature = "cart", feature = "miscellaneous", feature = "order"))]
 pub mod order_amount;
+#[cfg(any(feature = "cart", feature = "miscellaneous", feature = "order"))]
 pub use self::order_amount::OrderAmount;
+#[cfg(any(feature = "cart", feature = "miscellaneous", feature = "order"))]
 pub mod order_item;
+#[cfg(any(feature = "cart", feature = "miscellaneous", feature = "order"))]
 pub use self::order_item::OrderItem;
+#[cfg(any(feature = "cart", feature = "miscellaneous", feature = "order"))]
 pub mod order_shipping;
+#[cfg(any(feature = "cart", feature = "miscellaneous", feature = "order"))]
 pub use self::order_shipping::OrderShipping;
+#[cfg(any(feature = "cart", feature = "miscellaneous", feature = "order"))]
 pub mod order_tracking;
+#[cfg(any(feature = "cart", feature = "miscellaneous", feature = "order"))]
 pub use self::order_tracking::OrderTracking;
+#[cfg(feature = "address")]
 pub mod post_address_200_response;
+#[cfg(feature = "address")]
 pub use self::post_address_200_response::PostAddress200Response;
+#[cfg(feature = "address")]
 pub mod post_address_request;
+#[cfg(feature = "address")]
 pub use self::post_address_request::PostAddressRequest;
+#[cfg(feature = "app")]
 pub mod post_app_200_response;
+#[cfg(feature = "app")]
 pub use self::post_app_200_response::PostApp200Response;
+#[cfg(feature = "app")]
 pub mod post_app_200_response_data;
+#[cfg(feature = "app")]
 pub use self::post_app_200_response_data::PostApp200ResponseData;
+#[cfg(feature = "app")]
 pub mod post_app_request;
+#[cfg(feature = "app")]
 pub use self::post_app_request::PostAppRequest;
+#[cfg(feature = "card")]
 pub mod post_card_200_response;
+#[cfg(feature = "card")]
 pub use self::post_card_200_response::PostCard200Response;
+#[cfg(feature = "card")]
 pub mod post_card_collect_200_response;
+#[cfg(feature = "card")]
 pub use self::post_card_collect_200_response::PostCardCollect200Response;
+#[cfg(feature = "card")]
 pub mod post_card_collect_200_response_data;
+#[cfg(feature = "card")]
 pub use self::post_card_collect_200_response_data::PostCardCollect200ResponseData;
+#[cfg(feature = "card")]
 pub mod post_card_request;
+#[cfg(feature = "card")]
 pub use self::post_card_request::PostCardRequest;
+#[cfg(feature = "cart")]
 pub mod post_cart_convert_200_response;
+#[cfg(feature = "cart")]
 pub use self::post_cart_convert_200_response::PostCartConvert200Response;
+#[cfg(feature = "miscellaneous")]
 pub mod post_email_request;
+#[cfg(feature = "miscellaneous")]
 pub use self::post_email_request::PostEmailRequest;
+#[cfg(feature = "order")]
 pub mod post_order_200_response;
+#[cfg(feature = "order")]
 pub use self::post_order_200_response::PostOrder200Response;
+#[cfg(feature = "order")]
 pub mod post_order_request;
+#[cfg(feature = "order")]
 pub use self::post_order_request::PostOrderRequest;
+#[cfg(feature = "token")]
 pub mod post_token_200_response;
+#[cfg(feature = "token")]
 pub use self::post_token_200_response::PostToken200Response;
+#[cfg(feature = "token")]
 pub mod post_token_200_response_data;
+#[cfg(feature = "token")]
 pub use self::post_token_200_response_data::PostToken200ResponseData;
+#[cfg(any(feature = "miscellaneous", feature = "product"))]
 pub mod product;
+#[cfg(any(feature = "miscellaneous", feature = "product"))]
 pub use self::product::Product;
+#[cfg(any(feature = "miscellaneous", feature = "product"))]
 pub mod product_tags;
+#[cfg(any(feature = "miscellaneous", feature = "product"))]
 pub use self::product_tags::ProductTags;
+#[cfg(any(feature = "miscellaneous", feature = "product"))]
 pub mod product_variant;
+#[cfg(any(feature = "miscellaneous", feature = "product"))]
 pub use self::product_variant::ProductVariant;
+#[cfg(any(feature = "miscellaneous", feature = "profile"))]
 pub mod profile;
+#[cfg(any(feature = "miscellaneous", feature = "profile"))]
 pub use self::profile::Profile;
+#[cfg(feature = "cart")]
 pub mod put_cart_address_request;
+#[cfg(feature = "cart")]
 pub use self::put_cart_address_request::PutCartAddressRequest;
+#[cfg(feature = "cart")]
 pub mod put_cart_card_request;
+#[cfg(feature = "cart")]
 pub use self::put_cart_card_request::PutCartCardRequest;
+#[cfg(feature = "cart")]
 pub mod put_cart_item_200_response;
+#[cfg(feature = "cart")]
 pub use self::put_cart_item_200_response::PutCartItem200Response;
+#[cfg(feature = "cart")]
 pub mod put_cart_item_request;
+#[cfg(feature = "cart")]
 pub use self::put_cart_item_request::PutCartItemRequest;
+#[cfg(feature = "profile")]
 pub mod put_profile_200_response;
+#[cfg(feature = "profile")]
 pub use self::put_profile_200_response::PutProfile200Response;
+#[cfg(feature = "profile")]
 pub mod put_profile_request;
+#[cfg(feature = "profile")]
 pub use self::put_profile_request::PutProfileRequest;
+#[cfg(any(feature = "cart", feature = "miscellaneous", feature = "order", feature = "product"))]
 pub mod region;
+#[cfg(any(feature = "cart", feature = "miscellaneous", feature = "order", feature = "product"))]
 pub use self::region::Region;
+#[cfg(any(feature = "miscellaneous", feature = "product", feature = "subscription"))]
 pub mod subscription;
+#[cfg(any(feature = "miscellaneous", feature = "product", feature = "subscription"))]
 pub use self::subscription::Subscription;
+#[cfg(any(feature = "miscellaneous", feature = "product", feature = "subscription"))]
 pub mod subscription_schedule;
+#[cfg(any(feature = "miscellaneous", feature = "product", feature = "subscription"))]
 pub use self::subscription_schedule::SubscriptionSchedule;
+#[cfg(any(feature = "miscellaneous", feature = "product", feature = "subscription"))]
+pub use self::subscription_schedule::SubscriptionSchedule as Schedule;
+#[cfg(any(feature = "miscellaneous", feature = "product", feature = "subscription", feature = "token"))]
+pub mod timestamp;
+#[cfg(any(feature = "miscellaneous", feature = "product", feature = "subscription", feature = "token"))]
+pub use self::timestamp::Timestamp;
+#[cfg(any(feature = "miscellaneous", feature = "token"))]
 pub mod token;
+#[cfg(any(feature = "miscellaneous", feature = "token"))]
 pub use self::token::Token;
+#[cfg(any(feature = "miscellaneous", feature = "profile"))]
 pub mod user;
+#[cfg(any(feature = "miscellaneous", feature = "profile"))]
 pub use self::user::User;
+#[cfg(any(feature = "miscellaneous", feature = "product", feature = "subscription"))]
 pub mod weekly;
+#[cfg(any(feature = "miscellaneous", feature = "product", feature = "subscription"))]
 pub use self::weekly::Weekly;
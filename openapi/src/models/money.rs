@@ -0,0 +1,352 @@
+/*
+ * Terminal API
+ *
+ * Hand-maintained: this file is listed in `.openapi-generator-ignore` and is
+ * not produced by OpenAPI Generator.
+ */
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::models;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Currency : A currency that prices and totals can be denominated in.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Currency {
+    /// United States dollar. Every Terminal shop price is in USD today.
+    #[default]
+    Usd,
+}
+
+impl Currency {
+    /// ISO 4217 currency code, e.g. `USD`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Usd => "USD",
+        }
+    }
+
+    /// Symbol used when formatting amounts, e.g. `$`.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Usd => "$",
+        }
+    }
+
+    /// Number of minor units (cents) in one major unit, as a power of ten.
+    pub fn minor_unit_digits(&self) -> u32 {
+        match self {
+            Self::Usd => 2,
+        }
+    }
+
+    /// The currency the Terminal shop charges in for the given region.
+    ///
+    /// Both regions are billed in USD today; this is the single place to
+    /// change once regional pricing lands.
+    pub fn for_region(_region: &models::Region) -> Currency {
+        Self::Usd
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Locale : Conventions used when formatting a [`Money`] amount for display.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Locale {
+    /// `$1,234.56`
+    #[default]
+    EnUs,
+    /// `$1,234.56`
+    EnGb,
+    /// `1.234,56 $`
+    DeDe,
+    /// `1 234,56 $`
+    FrFr,
+}
+
+impl Locale {
+    fn group_separator(&self) -> &'static str {
+        match self {
+            Self::EnUs | Self::EnGb => ",",
+            Self::DeDe => ".",
+            // narrow no-break space, as used by CLDR for French
+            Self::FrFr => "\u{202f}",
+        }
+    }
+
+    fn decimal_separator(&self) -> &'static str {
+        match self {
+            Self::EnUs | Self::EnGb => ".",
+            Self::DeDe | Self::FrFr => ",",
+        }
+    }
+
+    fn symbol_first(&self) -> bool {
+        matches!(self, Self::EnUs | Self::EnGb)
+    }
+}
+
+/// Money : An amount of money stored as an integer number of minor units (cents).
+///
+/// On the wire a `Money` is a bare integer, exactly like the `i32` cent fields
+/// it replaces. Arithmetic between amounts in different currencies is refused:
+/// the `checked_*` methods return `None` and the operators panic.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Money {
+    cents: i64,
+    currency: Currency,
+}
+
+impl Money {
+    /// Creates an amount from a number of minor units in the given currency.
+    pub const fn new(cents: i64, currency: Currency) -> Money {
+        Money { cents, currency }
+    }
+
+    /// Creates a USD amount from a number of cents.
+    pub const fn from_cents(cents: i64) -> Money {
+        Money::new(cents, Currency::Usd)
+    }
+
+    /// A zero amount in the given currency.
+    pub const fn zero(currency: Currency) -> Money {
+        Money::new(0, currency)
+    }
+
+    /// The amount in minor units (cents).
+    pub const fn cents(&self) -> i64 {
+        self.cents
+    }
+
+    /// The currency this amount is denominated in.
+    pub const fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub const fn is_zero(&self) -> bool {
+        self.cents == 0
+    }
+
+    pub const fn is_negative(&self) -> bool {
+        self.cents < 0
+    }
+
+    /// Adds two amounts, returning `None` on overflow or a currency mismatch.
+    pub fn checked_add(self, rhs: Money) -> Option<Money> {
+        if self.currency != rhs.currency {
+            return None;
+        }
+        Some(Money::new(
+            self.cents.checked_add(rhs.cents)?,
+            self.currency,
+        ))
+    }
+
+    /// Subtracts two amounts, returning `None` on overflow or a currency mismatch.
+    pub fn checked_sub(self, rhs: Money) -> Option<Money> {
+        if self.currency != rhs.currency {
+            return None;
+        }
+        Some(Money::new(
+            self.cents.checked_sub(rhs.cents)?,
+            self.currency,
+        ))
+    }
+
+    /// Multiplies an amount by a quantity, returning `None` on overflow.
+    pub fn checked_mul(self, quantity: i64) -> Option<Money> {
+        Some(Money::new(self.cents.checked_mul(quantity)?, self.currency))
+    }
+
+    /// Negates an amount, returning `None` on overflow.
+    pub fn checked_neg(self) -> Option<Money> {
+        Some(Money::new(self.cents.checked_neg()?, self.currency))
+    }
+
+    /// Sums amounts in the given currency, returning `None` on overflow or if
+    /// any amount is in a different currency.
+    pub fn checked_sum<I>(iter: I, currency: Currency) -> Option<Money>
+    where
+        I: IntoIterator<Item = Money>,
+    {
+        iter.into_iter()
+            .try_fold(Money::zero(currency), Money::checked_add)
+    }
+
+    /// Formats the amount using the conventions of the given locale.
+    pub fn format(&self, locale: Locale) -> String {
+        let digits = self.currency.minor_unit_digits();
+        let scale = 10u64.pow(digits);
+        let abs = self.cents.unsigned_abs();
+        let major = (abs / scale).to_string();
+        let minor = abs % scale;
+
+        // group the digits in threes from the right; `major` is ASCII so the
+        // byte chunks are always valid UTF-8
+        let grouped = major
+            .as_bytes()
+            .rchunks(3)
+            .rev()
+            .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join(locale.group_separator());
+
+        let number = format!(
+            "{}{}{:0width$}",
+            grouped,
+            locale.decimal_separator(),
+            minor,
+            width = digits as usize
+        );
+        let sign = if self.is_negative() { "-" } else { "" };
+        let symbol = self.currency.symbol();
+
+        if locale.symbol_first() {
+            format!("{sign}{symbol}{number}")
+        } else {
+            format!("{sign}{number} {symbol}")
+        }
+    }
+}
+
+impl fmt::Display for Money {
+    /// Formats the amount for the `en-US` locale, e.g. `$1,234.56`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.format(Locale::EnUs))
+    }
+}
+
+impl PartialOrd for Money {
+    /// Amounts are only comparable when they share a currency.
+    fn partial_cmp(&self, other: &Money) -> Option<Ordering> {
+        (self.currency == other.currency).then(|| self.cents.cmp(&other.cents))
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, rhs: Money) -> Money {
+        self.checked_add(rhs)
+            .unwrap_or_else(|| panic!("cannot add {} to {}", rhs, self))
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, rhs: Money) -> Money {
+        self.checked_sub(rhs)
+            .unwrap_or_else(|| panic!("cannot subtract {} from {}", rhs, self))
+    }
+}
+
+impl Mul<i64> for Money {
+    type Output = Money;
+
+    fn mul(self, quantity: i64) -> Money {
+        self.checked_mul(quantity)
+            .unwrap_or_else(|| panic!("cannot multiply {} by {}", self, quantity))
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        self.checked_neg()
+            .unwrap_or_else(|| panic!("cannot negate {}", self))
+    }
+}
+
+impl Sum for Money {
+    /// Sums amounts, panicking on overflow or mixed currencies. The sum of no
+    /// amounts is zero in the default currency.
+    fn sum<I: Iterator<Item = Money>>(mut iter: I) -> Money {
+        match iter.next() {
+            Some(first) => iter.fold(first, Add::add),
+            None => Money::default(),
+        }
+    }
+}
+
+impl<'a> Sum<&'a Money> for Money {
+    fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Money {
+        iter.copied().sum()
+    }
+}
+
+impl From<i32> for Money {
+    /// Interprets a bare integer as USD cents, matching the wire format.
+    fn from(cents: i32) -> Money {
+        Money::from_cents(cents.into())
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.cents)
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Money, D::Error> {
+        i64::deserialize(deserializer).map(Money::from_cents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serde_round_trip() {
+        let money: Money = serde_json::from_str("2200").unwrap();
+        assert_eq!(money, Money::from_cents(2200));
+        assert_eq!(money.currency(), Currency::Usd);
+        assert_eq!(serde_json::to_string(&money).unwrap(), "2200");
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let a = Money::from_cents(150);
+        let b = Money::from_cents(275);
+        assert_eq!(a.checked_add(b), Some(Money::from_cents(425)));
+        assert_eq!(a.checked_sub(b), Some(Money::from_cents(-125)));
+        assert_eq!(a.checked_mul(3), Some(Money::from_cents(450)));
+        assert_eq!(Money::from_cents(i64::MAX).checked_add(a), None);
+        assert_eq!(a.checked_neg(), Some(Money::from_cents(-150)));
+        assert_eq!(Money::from_cents(i64::MIN).checked_neg(), None);
+        assert_eq!(
+            Money::checked_sum([a, b, a], Currency::Usd),
+            Some(Money::from_cents(575))
+        );
+        assert_eq!([a, b].iter().sum::<Money>(), Money::from_cents(425));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot negate")]
+    fn test_neg_overflow_panics() {
+        let _ = -Money::from_cents(i64::MIN);
+    }
+
+    #[test]
+    fn test_format() {
+        let money = Money::from_cents(123_456);
+        assert_eq!(money.to_string(), "$1,234.56");
+        assert_eq!(money.format(Locale::DeDe), "1.234,56 $");
+        assert_eq!(money.format(Locale::FrFr), "1\u{202f}234,56 $");
+        assert_eq!(Money::from_cents(-5).to_string(), "-$0.05");
+        assert_eq!(Money::from_cents(100_000_000).to_string(), "$1,000,000.00");
+    }
+}
@@ -0,0 +1,104 @@
+/*
+ * Terminal API
+ *
+ * Hand-maintained: this file is listed in `.openapi-generator-ignore` and is
+ * not produced by OpenAPI Generator.
+ */
+
+/// Defines a string enum sent by the server that tolerates values this client
+/// doesn't know about yet.
+///
+/// Besides the listed variants the enum gets an `Unknown(String)` variant that
+/// holds any other value and serializes it back unchanged, so a new region or
+/// error category from the server doesn't fail the whole response. The enum is
+/// `#[non_exhaustive]` so adding known variants later isn't a breaking change.
+macro_rules! open_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident => $value:literal,)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[non_exhaustive]
+        #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)+
+            /// A value this client doesn't know about, as sent by the server.
+            Unknown(String),
+        }
+
+        impl $name {
+            /// The value as sent over the wire.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)+
+                    Self::Unknown(value) => value,
+                }
+            }
+
+            pub fn is_unknown(&self) -> bool {
+                matches!(self, Self::Unknown(_))
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> $name {
+                match value {
+                    $($value => Self::$variant,)+
+                    other => Self::Unknown(other.to_owned()),
+                }
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> $name {
+                match $name::from(value.as_str()) {
+                    Self::Unknown(_) => Self::Unknown(value),
+                    known => known,
+                }
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<$name, D::Error> {
+                String::deserialize(deserializer).map($name::from)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{error_response, Region};
+
+    #[test]
+    fn test_known_values_round_trip() {
+        let region: Region = serde_json::from_str("\"na\"").unwrap();
+        assert_eq!(region, Region::Na);
+        assert_eq!(serde_json::to_string(&region).unwrap(), "\"na\"");
+    }
+
+    #[test]
+    fn test_unknown_values_round_trip() {
+        let region: Region = serde_json::from_str("\"apac\"").unwrap();
+        assert_eq!(region, Region::Unknown("apac".to_string()));
+        assert!(region.is_unknown());
+        assert_eq!(serde_json::to_string(&region).unwrap(), "\"apac\"");
+
+        let error_type: error_response::Type = serde_json::from_str("\"conflict\"").unwrap();
+        assert_eq!(error_type.to_string(), "conflict");
+    }
+}
@@ -16,7 +16,7 @@ use serde::{Deserialize, Serialize};
 pub struct Order {
     /// Unique object identifier. The format and length of IDs may change over time.
     #[serde(rename = "id")]
-    pub id: String,
+    pub id: models::OrderId,
     /// Zero-based index of the order for this user only.
     #[serde(rename = "index", skip_serializing_if = "Option::is_none")]
     pub index: Option<i32>,
@@ -33,7 +33,7 @@ pub struct Order {
 
 impl Order {
     /// An order from the Terminal shop.
-    pub fn new(id: String, shipping: models::OrderShipping, amount: models::OrderAmount, tracking: models::OrderTracking, items: Vec<models::OrderItem>) -> Order {
+    pub fn new(id: models::OrderId, shipping: models::OrderShipping, amount: models::OrderAmount, tracking: models::OrderTracking, items: Vec<models::OrderItem>) -> Order {
         Order {
             id,
             index: None,
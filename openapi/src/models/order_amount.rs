@@ -16,15 +16,15 @@ use serde::{Deserialize, Serialize};
 pub struct OrderAmount {
     /// Shipping amount of the order, in cents (USD).
     #[serde(rename = "shipping")]
-    pub shipping: i32,
+    pub shipping: models::Money,
     /// Subtotal amount of the order, in cents (USD).
     #[serde(rename = "subtotal")]
-    pub subtotal: i32,
+    pub subtotal: models::Money,
 }
 
 impl OrderAmount {
     /// The subtotal and shipping amounts of the order.
-    pub fn new(shipping: i32, subtotal: i32) -> OrderAmount {
+    pub fn new(shipping: models::Money, subtotal: models::Money) -> OrderAmount {
         OrderAmount {
             shipping,
             subtotal,
@@ -21,17 +21,17 @@ pub struct OrderItem {
     pub description: Option<String>,
     /// Amount of the item in the order, in cents (USD).
     #[serde(rename = "amount")]
-    pub amount: i32,
+    pub amount: models::Money,
     /// Quantity of the item in the order.
     #[serde(rename = "quantity")]
     pub quantity: i32,
     /// ID of the product variant of the item in the order.
     #[serde(rename = "productVariantID", skip_serializing_if = "Option::is_none")]
-    pub product_variant_id: Option<String>,
+    pub product_variant_id: Option<models::VariantId>,
 }
 
 impl OrderItem {
-    pub fn new(id: String, amount: i32, quantity: i32) -> OrderItem {
+    pub fn new(id: String, amount: models::Money, quantity: i32) -> OrderItem {
         OrderItem {
             id,
             description: None,
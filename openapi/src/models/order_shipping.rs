@@ -31,7 +31,7 @@ pub struct OrderShipping {
     pub province: Option<String>,
     /// ISO 3166-1 alpha-2 country code of the address.
     #[serde(rename = "country")]
-    pub country: String,
+    pub country: models::Country,
     /// Zip code of the address.
     #[serde(rename = "zip")]
     pub zip: String,
@@ -42,7 +42,7 @@ pub struct OrderShipping {
 
 impl OrderShipping {
     /// Shipping address of the order.
-    pub fn new(name: String, street1: String, city: String, country: String, zip: String) -> OrderShipping {
+    pub fn new(name: String, street1: String, city: String, country: models::Country, zip: String) -> OrderShipping {
         OrderShipping {
             name,
             street1,
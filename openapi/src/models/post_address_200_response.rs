@@ -15,11 +15,11 @@ use serde::{Deserialize, Serialize};
 pub struct PostAddress200Response {
     /// Shipping address ID.
     #[serde(rename = "data")]
-    pub data: String,
+    pub data: models::AddressId,
 }
 
 impl PostAddress200Response {
-    pub fn new(data: String) -> PostAddress200Response {
+    pub fn new(data: models::AddressId) -> PostAddress200Response {
         PostAddress200Response {
             data,
         }
@@ -31,7 +31,7 @@ pub struct PostAddressRequest {
     pub province: Option<String>,
     /// ISO 3166-1 alpha-2 country code of the address.
     #[serde(rename = "country")]
-    pub country: String,
+    pub country: models::CountryCode,
     /// Zip code of the address.
     #[serde(rename = "zip")]
     pub zip: String,
@@ -42,7 +42,7 @@ pub struct PostAddressRequest {
 
 impl PostAddressRequest {
     /// Address information.
-    pub fn new(name: String, street1: String, city: String, country: String, zip: String) -> PostAddressRequest {
+    pub fn new(name: String, street1: String, city: String, country: models::CountryCode, zip: String) -> PostAddressRequest {
         PostAddressRequest {
             name,
             street1,
@@ -54,5 +54,26 @@ impl PostAddressRequest {
             phone: None,
         }
     }
+
+    /// Checks the zip, province and phone against the rules for the address's
+    /// country and returns the address with them in canonical form: postal
+    /// codes formatted the way the country writes them, provinces as
+    /// subdivision codes and phone numbers in E.164.
+    pub fn normalize(self) -> Result<PostAddressRequest, models::AddressError> {
+        let zip = self.country.normalize_postal_code(&self.zip)?;
+        let province = self.country.normalize_province(self.province.as_deref())?;
+        let phone = self
+            .phone
+            .as_deref()
+            .map(|phone| self.country.normalize_phone(phone))
+            .transpose()?;
+
+        Ok(PostAddressRequest {
+            zip,
+            province,
+            phone,
+            ..self
+        })
+    }
 }
 
@@ -15,14 +15,14 @@ use serde::{Deserialize, Serialize};
 pub struct PostApp200ResponseData {
     /// OAuth 2.0 client ID.
     #[serde(rename = "id")]
-    pub id: String,
+    pub id: models::AppId,
     /// OAuth 2.0 client secret.
     #[serde(rename = "secret")]
     pub secret: String,
 }
 
 impl PostApp200ResponseData {
-    pub fn new(id: String, secret: String) -> PostApp200ResponseData {
+    pub fn new(id: models::AppId, secret: String) -> PostApp200ResponseData {
         PostApp200ResponseData {
             id,
             secret,
@@ -15,11 +15,11 @@ use serde::{Deserialize, Serialize};
 pub struct PostCard200Response {
     /// ID of the card.
     #[serde(rename = "data")]
-    pub data: String,
+    pub data: models::CardId,
 }
 
 impl PostCard200Response {
-    pub fn new(data: String) -> PostCard200Response {
+    pub fn new(data: models::CardId) -> PostCard200Response {
         PostCard200Response {
             data,
         }
@@ -15,11 +15,11 @@ use serde::{Deserialize, Serialize};
 pub struct PostOrder200Response {
     /// Order ID.
     #[serde(rename = "data")]
-    pub data: String,
+    pub data: models::OrderId,
 }
 
 impl PostOrder200Response {
-    pub fn new(data: String) -> PostOrder200Response {
+    pub fn new(data: models::OrderId) -> PostOrder200Response {
         PostOrder200Response {
             data,
         }
@@ -16,18 +16,18 @@ use serde::{Deserialize, Serialize};
 pub struct PostOrderRequest {
     /// Product variants to include in the order, along with their quantities.
     #[serde(rename = "variants")]
-    pub variants: std::collections::HashMap<String, i32>,
+    pub variants: std::collections::HashMap<models::VariantId, i32>,
     /// Card ID.
     #[serde(rename = "cardID")]
-    pub card_id: String,
+    pub card_id: models::CardId,
     /// Shipping address ID.
     #[serde(rename = "addressID")]
-    pub address_id: String,
+    pub address_id: models::AddressId,
 }
 
 impl PostOrderRequest {
     /// Order information.
-    pub fn new(variants: std::collections::HashMap<String, i32>, card_id: String, address_id: String) -> PostOrderRequest {
+    pub fn new(variants: std::collections::HashMap<models::VariantId, i32>, card_id: models::CardId, address_id: models::AddressId) -> PostOrderRequest {
         PostOrderRequest {
             variants,
             card_id,
@@ -15,14 +15,14 @@ use serde::{Deserialize, Serialize};
 pub struct PostToken200ResponseData {
     /// Personal token ID.
     #[serde(rename = "id")]
-    pub id: String,
+    pub id: models::TokenId,
     /// Personal access token. Include this in the Authorization header (`Bearer <token>`) when accessing the Terminal API.
     #[serde(rename = "token")]
     pub token: String,
 }
 
 impl PostToken200ResponseData {
-    pub fn new(id: String, token: String) -> PostToken200ResponseData {
+    pub fn new(id: models::TokenId, token: String) -> PostToken200ResponseData {
         PostToken200ResponseData {
             id,
             token,
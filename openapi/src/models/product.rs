@@ -16,7 +16,7 @@ use serde::{Deserialize, Serialize};
 pub struct Product {
     /// Unique object identifier. The format and length of IDs may change over time.
     #[serde(rename = "id")]
-    pub id: String,
+    pub id: models::ProductId,
     /// Name of the product.
     #[serde(rename = "name")]
     pub name: String,
@@ -38,7 +38,7 @@ pub struct Product {
 
 impl Product {
     /// Product sold in the Terminal shop.
-    pub fn new(id: String, name: String, description: String, variants: Vec<models::ProductVariant>) -> Product {
+    pub fn new(id: models::ProductId, name: String, description: String, variants: Vec<models::ProductVariant>) -> Product {
         Product {
             id,
             name,
@@ -50,13 +50,12 @@ impl Product {
         }
     }
 }
-/// Whether the product must be or can be subscribed to.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
-pub enum Subscription {
-    #[serde(rename = "allowed")]
-    Allowed,
-    #[serde(rename = "required")]
-    Required,
+open_enum! {
+    /// Whether the product must be or can be subscribed to.
+    pub enum Subscription {
+        Allowed => "allowed",
+        Required => "required",
+    }
 }
 
 impl Default for Subscription {
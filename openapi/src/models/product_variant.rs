@@ -16,18 +16,18 @@ use serde::{Deserialize, Serialize};
 pub struct ProductVariant {
     /// Unique object identifier. The format and length of IDs may change over time.
     #[serde(rename = "id")]
-    pub id: String,
+    pub id: models::VariantId,
     /// Name of the product variant.
     #[serde(rename = "name")]
     pub name: String,
     /// Price of the product variant in cents (USD).
     #[serde(rename = "price")]
-    pub price: i32,
+    pub price: models::Money,
 }
 
 impl ProductVariant {
     /// Variant of a product in the Terminal shop.
-    pub fn new(id: String, name: String, price: i32) -> ProductVariant {
+    pub fn new(id: models::VariantId, name: String, price: models::Money) -> ProductVariant {
         ProductVariant {
             id,
             name,
@@ -15,11 +15,11 @@ use serde::{Deserialize, Serialize};
 pub struct PutCartAddressRequest {
     /// ID of the shipping address to set for the current user's cart.
     #[serde(rename = "addressID")]
-    pub address_id: String,
+    pub address_id: models::AddressId,
 }
 
 impl PutCartAddressRequest {
-    pub fn new(address_id: String) -> PutCartAddressRequest {
+    pub fn new(address_id: models::AddressId) -> PutCartAddressRequest {
         PutCartAddressRequest {
             address_id,
         }
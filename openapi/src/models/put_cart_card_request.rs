@@ -15,11 +15,11 @@ use serde::{Deserialize, Serialize};
 pub struct PutCartCardRequest {
     /// ID of the credit card to set for the current user's cart.
     #[serde(rename = "cardID")]
-    pub card_id: String,
+    pub card_id: models::CardId,
 }
 
 impl PutCartCardRequest {
-    pub fn new(card_id: String) -> PutCartCardRequest {
+    pub fn new(card_id: models::CardId) -> PutCartCardRequest {
         PutCartCardRequest {
             card_id,
         }
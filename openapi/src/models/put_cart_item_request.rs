@@ -15,14 +15,14 @@ use serde::{Deserialize, Serialize};
 pub struct PutCartItemRequest {
     /// ID of the product variant to add to the cart.
     #[serde(rename = "productVariantID")]
-    pub product_variant_id: String,
+    pub product_variant_id: models::VariantId,
     /// Quantity of the item to add to the cart.
     #[serde(rename = "quantity")]
     pub quantity: i32,
 }
 
 impl PutCartItemRequest {
-    pub fn new(product_variant_id: String, quantity: i32) -> PutCartItemRequest {
+    pub fn new(product_variant_id: models::VariantId, quantity: i32) -> PutCartItemRequest {
         PutCartItemRequest {
             product_variant_id,
             quantity,
@@ -11,23 +11,12 @@
 use crate::models;
 use serde::{Deserialize, Serialize};
 
-/// Region : A Terminal shop user's region.
-/// A Terminal shop user's region.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
-pub enum Region {
-    #[serde(rename = "eu")]
-    Eu,
-    #[serde(rename = "na")]
-    Na,
-
-}
-
-impl std::fmt::Display for Region {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match self {
-            Self::Eu => write!(f, "eu"),
-            Self::Na => write!(f, "na"),
-        }
+open_enum! {
+    /// Region : A Terminal shop user's region.
+    /// A Terminal shop user's region.
+    pub enum Region {
+        Eu => "eu",
+        Na => "na",
     }
 }
 
@@ -16,29 +16,29 @@ use serde::{Deserialize, Serialize};
 pub struct Subscription {
     /// Unique object identifier. The format and length of IDs may change over time.
     #[serde(rename = "id")]
-    pub id: String,
+    pub id: models::SubscriptionId,
     /// ID of the product variant being subscribed to.
     #[serde(rename = "productVariantID")]
-    pub product_variant_id: String,
+    pub product_variant_id: models::VariantId,
     /// Quantity of the subscription.
     #[serde(rename = "quantity")]
     pub quantity: i32,
     /// ID of the shipping address used for the subscription.
     #[serde(rename = "addressID")]
-    pub address_id: String,
+    pub address_id: models::AddressId,
     /// ID of the card used for the subscription.
     #[serde(rename = "cardID")]
-    pub card_id: String,
+    pub card_id: models::CardId,
     #[serde(rename = "schedule", skip_serializing_if = "Option::is_none")]
     pub schedule: Option<Box<models::SubscriptionSchedule>>,
     /// Next shipment and billing date for the subscription.
     #[serde(rename = "next", skip_serializing_if = "Option::is_none")]
-    pub next: Option<String>,
+    pub next: Option<models::Timestamp>,
 }
 
 impl Subscription {
     /// Subscription to a Terminal shop product.
-    pub fn new(id: String, product_variant_id: String, quantity: i32, address_id: String, card_id: String) -> Subscription {
+    pub fn new(id: models::SubscriptionId, product_variant_id: models::VariantId, quantity: i32, address_id: models::AddressId, card_id: models::CardId) -> Subscription {
         Subscription {
             id,
             product_variant_id,
@@ -49,5 +49,14 @@ impl Subscription {
             next: None,
         }
     }
+
+    /// Whether the next shipment is due between `now` and `now + window`.
+    /// Always `false` if there is no next date or it couldn't be parsed.
+    #[cfg(feature = "chrono")]
+    pub fn ships_within(&self, window: chrono::TimeDelta, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.next
+            .as_ref()
+            .is_some_and(|next| next.is_within(window, now))
+    }
 }
 
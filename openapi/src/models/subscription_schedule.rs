@@ -1,42 +1,155 @@
 /*
  * Terminal API
  *
- * The Terminal API gives you access to the same API that powers the award winning Terminal SSH shop (`ssh terminal.shop`).
- *
- * The version of the OpenAPI document: 0.1.0
- * 
- * Generated by: https://openapi-generator.tech
+ * Hand-maintained: this file is listed in `.openapi-generator-ignore` and is
+ * not produced by OpenAPI Generator.
  */
 
 use crate::models;
-use serde::{Deserialize, Serialize};
+use serde::de::Error as _;
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// SubscriptionSchedule : Schedule of the subscription.
-/// Schedule of the subscription.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-#[serde(untagged)]
+///
+/// On the wire this is an object tagged by its `type` field, e.g.
+/// `{"type": "weekly", "interval": 2}`. Schedule types this client doesn't know
+/// about are kept as [`SubscriptionSchedule::Unknown`] instead of failing to
+/// parse the whole subscription, and are serialized back unchanged.
+#[non_exhaustive]
+#[derive(Clone, Default, Debug, PartialEq)]
 pub enum SubscriptionSchedule {
-    Fixed(Box<models::Fixed>),
-    Weekly(Box<models::Weekly>),
+    /// Ships once, with no recurring interval.
+    #[default]
+    Fixed,
+    /// Ships every `interval` weeks.
+    Weekly { interval: i32 },
+    /// A schedule type this client doesn't know about, as sent by the server.
+    Unknown(serde_json::Value),
 }
 
-impl Default for SubscriptionSchedule {
-    fn default() -> Self {
-        Self::Fixed(Default::default())
+impl SubscriptionSchedule {
+    pub fn fixed() -> SubscriptionSchedule {
+        Self::Fixed
+    }
+
+    /// A schedule that ships every `interval` weeks.
+    pub fn weekly(interval: i32) -> SubscriptionSchedule {
+        Self::Weekly { interval }
+    }
+
+    /// The value of the `type` tag, e.g. `weekly`.
+    pub fn kind(&self) -> Option<&str> {
+        match self {
+            Self::Fixed => Some("fixed"),
+            Self::Weekly { .. } => Some("weekly"),
+            Self::Unknown(value) => value.get("type").and_then(|t| t.as_str()),
+        }
+    }
+
+    /// The number of weeks between shipments, for schedules that have one.
+    pub fn interval(&self) -> Option<i32> {
+        match self {
+            Self::Fixed => None,
+            Self::Weekly { interval } => Some(*interval),
+            Self::Unknown(value) => value
+                .get("interval")
+                .and_then(|i| i.as_i64())
+                .and_then(|i| i32::try_from(i).ok()),
+        }
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, Self::Unknown(_))
     }
-}
-/// 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
-pub enum Type {
-    #[serde(rename = "fixed")]
-    Fixed,
-    #[serde(rename = "weekly")]
-    Weekly,
 }
 
-impl Default for Type {
-    fn default() -> Type {
+impl From<models::Fixed> for SubscriptionSchedule {
+    fn from(_: models::Fixed) -> SubscriptionSchedule {
         Self::Fixed
     }
 }
 
+impl From<models::Weekly> for SubscriptionSchedule {
+    fn from(weekly: models::Weekly) -> SubscriptionSchedule {
+        Self::Weekly {
+            interval: weekly.interval,
+        }
+    }
+}
+
+impl Serialize for SubscriptionSchedule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Fixed => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("type", "fixed")?;
+                map.end()
+            }
+            Self::Weekly { interval } => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("type", "weekly")?;
+                map.serialize_entry("interval", interval)?;
+                map.end()
+            }
+            Self::Unknown(value) => value.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SubscriptionSchedule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+
+        match value.get("type").and_then(|t| t.as_str()) {
+            Some("fixed") => Ok(Self::Fixed),
+            Some("weekly") => {
+                let interval = value
+                    .get("interval")
+                    .ok_or_else(|| D::Error::missing_field("interval"))?;
+                let interval = i32::deserialize(interval).map_err(D::Error::custom)?;
+                Ok(Self::Weekly { interval })
+            }
+            _ => Ok(Self::Unknown(value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_known_schedules_round_trip() {
+        for (schedule, value) in [
+            (SubscriptionSchedule::fixed(), json!({ "type": "fixed" })),
+            (
+                SubscriptionSchedule::weekly(2),
+                json!({ "type": "weekly", "interval": 2 }),
+            ),
+        ] {
+            assert_eq!(serde_json::to_value(&schedule).unwrap(), value);
+            assert_eq!(
+                serde_json::from_value::<SubscriptionSchedule>(value).unwrap(),
+                schedule
+            );
+        }
+    }
+
+    #[test]
+    fn test_unknown_schedule_is_kept() {
+        let value = json!({ "type": "monthly", "interval": 1, "day": 15 });
+        let schedule: SubscriptionSchedule = serde_json::from_value(value.clone()).unwrap();
+        assert!(schedule.is_unknown());
+        assert_eq!(schedule.kind(), Some("monthly"));
+        assert_eq!(schedule.interval(), Some(1));
+        assert_eq!(serde_json::to_value(&schedule).unwrap(), value);
+    }
+
+    #[test]
+    fn test_malformed_weekly_schedule_is_an_error() {
+        let result = serde_json::from_value::<SubscriptionSchedule>(json!({ "type": "weekly" }));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,157 @@
+/*
+ * Terminal API
+ *
+ * Hand-maintained: this file is listed in `.openapi-generator-ignore` and is
+ * not produced by OpenAPI Generator.
+ */
+
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+
+/// Timestamp : A date/time sent by the server, such as when a token was created.
+///
+/// The string the server sent is always kept and is what gets serialized, so a
+/// `Timestamp` round-trips losslessly even if the server changes its format.
+/// With the `chrono` feature enabled the string is also parsed (RFC 3339, or a
+/// bare `YYYY-MM-DD` date taken as midnight UTC) and exposed through
+/// [`Timestamp::datetime`]; a string in any other format stays available via
+/// [`Timestamp::as_str`].
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash)]
+pub struct Timestamp {
+    raw: String,
+    #[cfg(feature = "chrono")]
+    parsed: Option<DateTime<Utc>>,
+}
+
+impl Timestamp {
+    /// Creates a timestamp from the string representation sent by the server.
+    pub fn new(raw: impl Into<String>) -> Timestamp {
+        let raw = raw.into();
+        Timestamp {
+            #[cfg(feature = "chrono")]
+            parsed: parse(&raw),
+            raw,
+        }
+    }
+
+    /// The timestamp exactly as the server sent it.
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// The parsed date/time, or `None` if the server sent a format this client
+    /// doesn't understand.
+    #[cfg(feature = "chrono")]
+    pub fn datetime(&self) -> Option<DateTime<Utc>> {
+        self.parsed
+    }
+
+    /// Whether this timestamp falls between `now` and `now + window`
+    /// (inclusive). A window reaching past the latest representable date has
+    /// no upper bound. Always `false` if the timestamp couldn't be parsed.
+    #[cfg(feature = "chrono")]
+    pub fn is_within(&self, window: TimeDelta, now: DateTime<Utc>) -> bool {
+        if window < TimeDelta::zero() {
+            return false;
+        }
+        let end = now.checked_add_signed(window);
+        self.parsed
+            .is_some_and(|at| at >= now && end.is_none_or(|end| at <= end))
+    }
+
+    /// Whether this timestamp is before `now`. Always `false` if the timestamp
+    /// couldn't be parsed.
+    #[cfg(feature = "chrono")]
+    pub fn is_before(&self, now: DateTime<Utc>) -> bool {
+        self.parsed.is_some_and(|at| at < now)
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn parse(raw: &str) -> Option<DateTime<Utc>> {
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(raw) {
+        return Some(datetime.with_timezone(&Utc));
+    }
+
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| datetime.and_utc())
+}
+
+#[cfg(feature = "chrono")]
+impl From<DateTime<Utc>> for Timestamp {
+    fn from(datetime: DateTime<Utc>) -> Timestamp {
+        Timestamp {
+            raw: datetime.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+            parsed: Some(datetime),
+        }
+    }
+}
+
+impl From<String> for Timestamp {
+    fn from(raw: String) -> Timestamp {
+        Timestamp::new(raw)
+    }
+}
+
+impl From<&str> for Timestamp {
+    fn from(raw: &str) -> Timestamp {
+        Timestamp::new(raw)
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Timestamp, D::Error> {
+        String::deserialize(deserializer).map(Timestamp::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_is_lossless() {
+        for raw in ["2024-06-29T19:36:19.000Z", "2024-06-29", "next tuesday"] {
+            let json = format!("\"{}\"", raw);
+            let timestamp: Timestamp = serde_json::from_str(&json).unwrap();
+            assert_eq!(timestamp.as_str(), raw);
+            assert_eq!(serde_json::to_string(&timestamp).unwrap(), json);
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_parsing() {
+        let now = Timestamp::new("2024-06-01T00:00:00Z").datetime().unwrap();
+
+        let next = Timestamp::new("2024-06-03");
+        assert!(next.datetime().is_some());
+        assert!(next.is_within(TimeDelta::days(2), now));
+        assert!(!next.is_within(TimeDelta::days(1), now));
+        assert!(!next.is_before(now));
+        assert!(next.is_within(TimeDelta::MAX, now));
+        assert!(!next.is_within(TimeDelta::days(-1), now));
+
+        let unknown = Timestamp::new("next tuesday");
+        assert_eq!(unknown.datetime(), None);
+        assert!(!unknown.is_within(TimeDelta::days(365), now));
+    }
+}
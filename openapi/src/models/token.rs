@@ -16,10 +16,10 @@ use serde::{Deserialize, Serialize};
 pub struct Token {
     /// Unique object identifier. The format and length of IDs may change over time.
     #[serde(rename = "id")]
-    pub id: String,
+    pub id: models::TokenId,
     /// The created time for the token.
     #[serde(rename = "created")]
-    pub created: String,
+    pub created: models::Timestamp,
     /// Personal access token (obfuscated).
     #[serde(rename = "token")]
     pub token: String,
@@ -27,7 +27,7 @@ pub struct Token {
 
 impl Token {
     /// A personal access token used to access the Terminal API. If you leak this, expect large sums of coffee to be ordered on your credit card.
-    pub fn new(id: String, created: String, token: String) -> Token {
+    pub fn new(id: models::TokenId, created: models::Timestamp, token: String) -> Token {
         Token {
             id,
             created,
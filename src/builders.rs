@@ -0,0 +1,580 @@
+//! Builders for request models
+//!
+//! Required fields are tracked in the builder's type, so `build()` only exists
+//! once every required field has been set. Forgetting one is a compile error
+//! rather than a `400` from the API:
+//!
+//! ```
+//! use terminal::builders::PostOrderRequestBuilder;
+//!
+//! let order = PostOrderRequestBuilder::new()
+//!     .variant("var_XXXXXXXXXXXXXXXXXXXXXXXXX", 2)
+//!     .card("crd_XXXXXXXXXXXXXXXXXXXXXXXXX")
+//!     .address("shp_XXXXXXXXXXXXXXXXXXXXXXXXX")
+//!     .build()
+//!     .unwrap();
+//! # let _ = order;
+//! ```
+//!
+//! `build()` also checks the values themselves (non-empty strings, positive
+//! quantities, ...) and returns a [`ValidationError`] if any are invalid.
+
+#[cfg(feature = "order")]
+use std::collections::HashMap;
+use std::fmt;
+
+#[cfg(feature = "order")]
+use crate::models::PostOrderRequest;
+#[cfg(feature = "address")]
+use crate::models::{AddressError, CountryCode, PostAddressRequest};
+#[cfg(any(feature = "order", feature = "subscription"))]
+use crate::models::{AddressId, CardId, VariantId};
+#[cfg(feature = "subscription")]
+use crate::models::{Subscription, SubscriptionSchedule};
+
+/// Marks a required builder field that hasn't been set yet
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Unset;
+
+/// Returned by a builder's `build()` when a field has an invalid value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// name of the offending field, as sent over the wire
+    pub field: &'static str,
+    /// what is wrong with the field
+    pub reason: String,
+}
+
+impl ValidationError {
+    fn new(field: &'static str, reason: impl Into<String>) -> Self {
+        ValidationError {
+            field,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid `{}`: {}", self.field, self.reason)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+#[cfg(feature = "address")]
+impl From<AddressError> for ValidationError {
+    fn from(err: AddressError) -> Self {
+        ValidationError::new(err.field(), err.to_string())
+    }
+}
+
+fn require_non_empty(field: &'static str, value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::new(field, "must not be empty"));
+    }
+    Ok(())
+}
+
+#[cfg(any(feature = "order", feature = "subscription"))]
+fn require_positive(field: &'static str, value: i32) -> Result<(), ValidationError> {
+    if value <= 0 {
+        return Err(ValidationError::new(
+            field,
+            format!("must be positive, got {}", value),
+        ));
+    }
+    Ok(())
+}
+
+/// Builds a [`PostAddressRequest`]
+///
+/// `name`, `street1`, `city`, `country` and `zip` are required. On `build()`
+/// the zip, province and phone are checked against the country's rules and
+/// normalized, see [`PostAddressRequest::normalize`].
+#[cfg(feature = "address")]
+#[derive(Debug, Clone)]
+pub struct PostAddressRequestBuilder<
+    Name = Unset,
+    Street = Unset,
+    City = Unset,
+    Country = Unset,
+    Zip = Unset,
+> {
+    name: Name,
+    street1: Street,
+    street2: Option<String>,
+    city: City,
+    province: Option<String>,
+    country: Country,
+    zip: Zip,
+    phone: Option<String>,
+}
+
+#[cfg(feature = "address")]
+impl PostAddressRequestBuilder {
+    pub fn new() -> Self {
+        PostAddressRequestBuilder {
+            name: Unset,
+            street1: Unset,
+            street2: None,
+            city: Unset,
+            province: None,
+            country: Unset,
+            zip: Unset,
+            phone: None,
+        }
+    }
+}
+
+#[cfg(feature = "address")]
+impl Default for PostAddressRequestBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "address")]
+impl<N, S, C, Co, Z> PostAddressRequestBuilder<N, S, C, Co, Z> {
+    /// The recipient's name
+    pub fn name(self, name: impl Into<String>) -> PostAddressRequestBuilder<String, S, C, Co, Z> {
+        PostAddressRequestBuilder {
+            name: name.into(),
+            street1: self.street1,
+            street2: self.street2,
+            city: self.city,
+            province: self.province,
+            country: self.country,
+            zip: self.zip,
+            phone: self.phone,
+        }
+    }
+
+    /// Street of the address
+    pub fn street1(
+        self,
+        street1: impl Into<String>,
+    ) -> PostAddressRequestBuilder<N, String, C, Co, Z> {
+        PostAddressRequestBuilder {
+            name: self.name,
+            street1: street1.into(),
+            street2: self.street2,
+            city: self.city,
+            province: self.province,
+            country: self.country,
+            zip: self.zip,
+            phone: self.phone,
+        }
+    }
+
+    /// Apartment, suite, etc. of the address
+    pub fn street2(mut self, street2: impl Into<String>) -> Self {
+        self.street2 = Some(street2.into());
+        self
+    }
+
+    /// City of the address
+    pub fn city(self, city: impl Into<String>) -> PostAddressRequestBuilder<N, S, String, Co, Z> {
+        PostAddressRequestBuilder {
+            name: self.name,
+            street1: self.street1,
+            street2: self.street2,
+            city: city.into(),
+            province: self.province,
+            country: self.country,
+            zip: self.zip,
+            phone: self.phone,
+        }
+    }
+
+    /// Province or state of the address
+    pub fn province(mut self, province: impl Into<String>) -> Self {
+        self.province = Some(province.into());
+        self
+    }
+
+    /// ISO 3166-1 alpha-2 country code of the address
+    pub fn country(
+        self,
+        country: impl Into<String>,
+    ) -> PostAddressRequestBuilder<N, S, C, String, Z> {
+        PostAddressRequestBuilder {
+            name: self.name,
+            street1: self.street1,
+            street2: self.street2,
+            city: self.city,
+            province: self.province,
+            country: country.into(),
+            zip: self.zip,
+            phone: self.phone,
+        }
+    }
+
+    /// Zip code of the address
+    pub fn zip(self, zip: impl Into<String>) -> PostAddressRequestBuilder<N, S, C, Co, String> {
+        PostAddressRequestBuilder {
+            name: self.name,
+            street1: self.street1,
+            street2: self.street2,
+            city: self.city,
+            province: self.province,
+            country: self.country,
+            zip: zip.into(),
+            phone: self.phone,
+        }
+    }
+
+    /// Phone number of the recipient
+    pub fn phone(mut self, phone: impl Into<String>) -> Self {
+        self.phone = Some(phone.into());
+        self
+    }
+}
+
+#[cfg(feature = "address")]
+impl PostAddressRequestBuilder<String, String, String, String, String> {
+    /// Validates the fields and builds the request
+    pub fn build(self) -> Result<PostAddressRequest, ValidationError> {
+        require_non_empty("name", &self.name)?;
+        require_non_empty("street1", &self.street1)?;
+        require_non_empty("city", &self.city)?;
+
+        let request = PostAddressRequest {
+            name: self.name,
+            street1: self.street1,
+            street2: self.street2,
+            city: self.city,
+            province: self.province,
+            country: CountryCode::new(&self.country)?,
+            zip: self.zip,
+            phone: self.phone,
+        };
+
+        Ok(request.normalize()?)
+    }
+}
+
+/// Builds a [`PostOrderRequest`]
+///
+/// A card, an address and at least one variant are required.
+#[cfg(feature = "order")]
+#[derive(Debug, Clone)]
+pub struct PostOrderRequestBuilder<Card = Unset, Address = Unset> {
+    variants: HashMap<VariantId, i32>,
+    /// a variant whose quantity no longer fits in an `i32`
+    overflowed: Option<VariantId>,
+    card_id: Card,
+    address_id: Address,
+}
+
+#[cfg(feature = "order")]
+impl PostOrderRequestBuilder {
+    pub fn new() -> Self {
+        PostOrderRequestBuilder {
+            variants: HashMap::new(),
+            overflowed: None,
+            card_id: Unset,
+            address_id: Unset,
+        }
+    }
+}
+
+#[cfg(feature = "order")]
+impl Default for PostOrderRequestBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "order")]
+impl<C, A> PostOrderRequestBuilder<C, A> {
+    /// Adds `quantity` of a product variant to the order. Adding the same
+    /// variant again increases its quantity; `build()` fails if the total
+    /// overflows.
+    pub fn variant(mut self, id: impl Into<VariantId>, quantity: i32) -> Self {
+        let id = id.into();
+        let total = self.variants.entry(id.clone()).or_default();
+        match total.checked_add(quantity) {
+            Some(sum) => *total = sum,
+            None => {
+                self.overflowed.get_or_insert(id);
+            }
+        }
+        self
+    }
+
+    /// The card to charge for the order
+    pub fn card(self, id: impl Into<CardId>) -> PostOrderRequestBuilder<CardId, A> {
+        PostOrderRequestBuilder {
+            variants: self.variants,
+            overflowed: self.overflowed,
+            card_id: id.into(),
+            address_id: self.address_id,
+        }
+    }
+
+    /// The address to ship the order to
+    pub fn address(self, id: impl Into<AddressId>) -> PostOrderRequestBuilder<C, AddressId> {
+        PostOrderRequestBuilder {
+            variants: self.variants,
+            overflowed: self.overflowed,
+            card_id: self.card_id,
+            address_id: id.into(),
+        }
+    }
+}
+
+#[cfg(feature = "order")]
+impl PostOrderRequestBuilder<CardId, AddressId> {
+    /// Validates the fields and builds the request
+    pub fn build(self) -> Result<PostOrderRequest, ValidationError> {
+        if self.variants.is_empty() {
+            return Err(ValidationError::new(
+                "variants",
+                "must contain at least one variant",
+            ));
+        }
+        if let Some(id) = self.overflowed {
+            return Err(ValidationError::new(
+                "variants",
+                format!("quantity of {} is too large", id),
+            ));
+        }
+        for quantity in self.variants.values() {
+            require_positive("variants", *quantity)?;
+        }
+        require_non_empty("cardID", self.card_id.as_str())?;
+        require_non_empty("addressID", self.address_id.as_str())?;
+
+        Ok(PostOrderRequest::new(
+            self.variants,
+            self.card_id,
+            self.address_id,
+        ))
+    }
+}
+
+/// Builds a [`Subscription`] to pass to `post_subscription`
+///
+/// A product variant, a card and an address are required. The quantity
+/// defaults to 1 and the ID is left empty for the server to assign.
+#[cfg(feature = "subscription")]
+#[derive(Debug, Clone)]
+pub struct SubscriptionBuilder<Variant = Unset, Card = Unset, Address = Unset> {
+    product_variant_id: Variant,
+    quantity: i32,
+    card_id: Card,
+    address_id: Address,
+    schedule: Option<SubscriptionSchedule>,
+}
+
+#[cfg(feature = "subscription")]
+impl SubscriptionBuilder {
+    pub fn new() -> Self {
+        SubscriptionBuilder {
+            product_variant_id: Unset,
+            quantity: 1,
+            card_id: Unset,
+            address_id: Unset,
+            schedule: None,
+        }
+    }
+}
+
+#[cfg(feature = "subscription")]
+impl Default for SubscriptionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "subscription")]
+impl<V, C, A> SubscriptionBuilder<V, C, A> {
+    /// The product variant to subscribe to
+    pub fn variant(self, id: impl Into<VariantId>) -> SubscriptionBuilder<VariantId, C, A> {
+        SubscriptionBuilder {
+            product_variant_id: id.into(),
+            quantity: self.quantity,
+            card_id: self.card_id,
+            address_id: self.address_id,
+            schedule: self.schedule,
+        }
+    }
+
+    /// How many of the variant to ship each time
+    pub fn quantity(mut self, quantity: i32) -> Self {
+        self.quantity = quantity;
+        self
+    }
+
+    /// The card to charge for each shipment
+    pub fn card(self, id: impl Into<CardId>) -> SubscriptionBuilder<V, CardId, A> {
+        SubscriptionBuilder {
+            product_variant_id: self.product_variant_id,
+            quantity: self.quantity,
+            card_id: id.into(),
+            address_id: self.address_id,
+            schedule: self.schedule,
+        }
+    }
+
+    /// The address to ship to
+    pub fn address(self, id: impl Into<AddressId>) -> SubscriptionBuilder<V, C, AddressId> {
+        SubscriptionBuilder {
+            product_variant_id: self.product_variant_id,
+            quantity: self.quantity,
+            card_id: self.card_id,
+            address_id: id.into(),
+            schedule: self.schedule,
+        }
+    }
+
+    /// How often to ship, left to the API's default if unset
+    pub fn schedule(mut self, schedule: SubscriptionSchedule) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    /// Ship once, with no recurring interval
+    pub fn schedule_fixed(self) -> Self {
+        self.schedule(SubscriptionSchedule::fixed())
+    }
+
+    /// Ship every `interval` weeks
+    pub fn schedule_weekly(self, interval: i32) -> Self {
+        self.schedule(SubscriptionSchedule::weekly(interval))
+    }
+}
+
+#[cfg(feature = "subscription")]
+impl SubscriptionBuilder<VariantId, CardId, AddressId> {
+    /// Validates the fields and builds the subscription
+    pub fn build(self) -> Result<Subscription, ValidationError> {
+        require_non_empty("productVariantID", self.product_variant_id.as_str())?;
+        require_positive("quantity", self.quantity)?;
+        require_non_empty("cardID", self.card_id.as_str())?;
+        require_non_empty("addressID", self.address_id.as_str())?;
+        if let Some(interval) = self.schedule.as_ref().and_then(|s| s.interval()) {
+            require_positive("schedule.interval", interval)?;
+        }
+
+        let mut subscription = Subscription::new(
+            Default::default(),
+            self.product_variant_id,
+            self.quantity,
+            self.address_id,
+            self.card_id,
+        );
+        subscription.schedule = self.schedule.map(Box::new);
+
+        Ok(subscription)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "address")]
+    fn test_post_address_request_builder() {
+        let address = PostAddressRequestBuilder::new()
+            .name("John Doe")
+            .street1("1 Main St")
+            .city("Anytown")
+            .province("california")
+            .country("us")
+            .zip("12345")
+            .phone("(555) 555-0123")
+            .build()
+            .unwrap();
+        assert_eq!(address.country, CountryCode::US);
+        assert_eq!(address.province.as_deref(), Some("CA"));
+        assert_eq!(address.phone.as_deref(), Some("+15555550123"));
+        assert_eq!(address.street2, None);
+
+        let err = PostAddressRequestBuilder::new()
+            .name("John Doe")
+            .street1("1 Main St")
+            .city("Anytown")
+            .country("USA")
+            .zip("12345")
+            .build()
+            .unwrap_err();
+        assert_eq!(err.field, "country");
+
+        let err = PostAddressRequestBuilder::new()
+            .name("John Doe")
+            .street1("1 Main St")
+            .city("Anytown")
+            .country("US")
+            .zip("12345")
+            .build()
+            .unwrap_err();
+        assert_eq!(err.field, "province");
+    }
+
+    #[test]
+    #[cfg(feature = "order")]
+    fn test_post_order_request_builder() {
+        let order = PostOrderRequestBuilder::new()
+            .variant("var_1", 1)
+            .variant("var_2", 2)
+            .variant("var_1", 1)
+            .card("crd_1")
+            .address("shp_1")
+            .build()
+            .unwrap();
+        assert_eq!(order.variants.len(), 2);
+        assert_eq!(order.variants.get("var_1"), Some(&2));
+        assert_eq!(order.card_id, "crd_1");
+
+        let err = PostOrderRequestBuilder::new()
+            .card("crd_1")
+            .address("shp_1")
+            .build()
+            .unwrap_err();
+        assert_eq!(err.field, "variants");
+
+        let err = PostOrderRequestBuilder::new()
+            .variant("var_1", 0)
+            .card("crd_1")
+            .address("shp_1")
+            .build()
+            .unwrap_err();
+        assert_eq!(err.field, "variants");
+
+        let err = PostOrderRequestBuilder::new()
+            .variant("var_1", i32::MAX)
+            .variant("var_1", 1)
+            .card("crd_1")
+            .address("shp_1")
+            .build()
+            .unwrap_err();
+        assert_eq!(err.field, "variants");
+        assert!(err.reason.contains("var_1"));
+    }
+
+    #[test]
+    #[cfg(feature = "subscription")]
+    fn test_subscription_builder() {
+        let subscription = SubscriptionBuilder::new()
+            .variant("var_1")
+            .card("crd_1")
+            .address("shp_1")
+            .schedule_weekly(2)
+            .build()
+            .unwrap();
+        assert_eq!(subscription.quantity, 1);
+        assert_eq!(subscription.schedule.unwrap().interval(), Some(2));
+
+        let err = SubscriptionBuilder::new()
+            .variant("var_1")
+            .card("crd_1")
+            .address("shp_1")
+            .schedule_weekly(0)
+            .build()
+            .unwrap_err();
+        assert_eq!(err.field, "schedule.interval");
+    }
+}
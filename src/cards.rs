@@ -0,0 +1,134 @@
+//! Picking a card to pay with
+//!
+//! ```
+//! use std::time::{Duration, SystemTime};
+//! use terminal::cards::CardSelector;
+//! use terminal::models::{Card, CardBrand};
+//!
+//! # let cards: Vec<Card> = vec![];
+//! let selector = CardSelector::new()
+//!     .prefer_brand(CardBrand::Visa)
+//!     .valid_for(Duration::from_secs(7 * 24 * 60 * 60));
+//! let card = selector.select(&cards, SystemTime::now());
+//! # assert!(card.is_none());
+//! ```
+
+use std::time::{Duration, SystemTime};
+
+use crate::models::{Card, CardBrand};
+
+/// A policy for choosing the default card for checkout
+///
+/// Expired cards, and cards that expire within the configured window, are never
+/// chosen. Among the rest, cards of a preferred brand win (earlier preferences
+/// first), then the most recently added card. The API doesn't say when a card
+/// was added, so "most recently added" means the last one in the list it
+/// returns.
+#[derive(Debug, Clone, Default)]
+pub struct CardSelector {
+    preferred_brands: Vec<CardBrand>,
+    valid_for: Duration,
+}
+
+impl CardSelector {
+    /// A selector that picks the most recently added card that hasn't expired
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prefers cards of the given brand. Can be called more than once; brands
+    /// added first are preferred most
+    pub fn prefer_brand(mut self, brand: CardBrand) -> Self {
+        self.preferred_brands.push(brand);
+        self
+    }
+
+    /// Skips cards that expire within `window`, e.g. so a subscription isn't
+    /// set up on a card that is about to stop working
+    pub fn valid_for(mut self, window: Duration) -> Self {
+        self.valid_for = window;
+        self
+    }
+
+    /// Whether the card can be used at all under this policy
+    pub fn is_eligible(&self, card: &Card, today: SystemTime) -> bool {
+        !card.is_expired(today) && !card.expires_within(self.valid_for, today)
+    }
+
+    /// Picks the best card from `cards`, as returned by `get_card`, or `None`
+    /// if no card is eligible
+    pub fn select<'a>(&self, cards: &'a [Card], today: SystemTime) -> Option<&'a Card> {
+        cards
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| self.is_eligible(card, today))
+            // lowest brand rank wins, then the latest position in the list
+            .min_by_key(|(position, card)| {
+                (self.brand_rank(&card.brand), std::cmp::Reverse(*position))
+            })
+            .map(|(_, card)| card)
+    }
+
+    fn brand_rank(&self, brand: &CardBrand) -> usize {
+        self.preferred_brands
+            .iter()
+            .position(|preferred| preferred == brand)
+            .unwrap_or(self.preferred_brands.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CardExpiration;
+    use std::time::UNIX_EPOCH;
+
+    fn card(id: &str, brand: CardBrand, year: i32, month: i32) -> Card {
+        Card::new(
+            id.into(),
+            brand,
+            CardExpiration::new(year, month),
+            "4242".to_string(),
+        )
+    }
+
+    /// 2026-10-01T00:00:00Z
+    fn today() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_790_812_800)
+    }
+
+    #[test]
+    fn test_masked() {
+        let card = card("crd_1", CardBrand::Visa, 2027, 4);
+        assert_eq!(card.to_string(), "Visa •••• 4242, exp 04/27");
+    }
+
+    #[test]
+    fn test_select() {
+        let cards = vec![
+            card("crd_old_visa", CardBrand::Visa, 2030, 1),
+            card("crd_expired", CardBrand::Visa, 2026, 9),
+            card("crd_amex", CardBrand::AmericanExpress, 2030, 1),
+            card("crd_new_visa", CardBrand::Visa, 2030, 1),
+            card("crd_expiring_mc", CardBrand::Mastercard, 2026, 10),
+        ];
+
+        // no preference: the newest card that hasn't expired
+        let selected = CardSelector::new().select(&cards, today()).unwrap();
+        assert_eq!(selected.id, "crd_expiring_mc");
+
+        // skip cards about to expire
+        let selector = CardSelector::new().valid_for(Duration::from_secs(60 * 86_400));
+        assert_eq!(selector.select(&cards, today()).unwrap().id, "crd_new_visa");
+        let selector = CardSelector::new().valid_for(Duration::MAX);
+        assert!(selector.select(&cards, today()).is_none());
+
+        // brand preference beats recency
+        let selector = CardSelector::new()
+            .prefer_brand(CardBrand::AmericanExpress)
+            .prefer_brand(CardBrand::Visa);
+        assert_eq!(selector.select(&cards, today()).unwrap().id, "crd_amex");
+
+        assert!(CardSelector::new().select(&cards[1..2], today()).is_none());
+    }
+}
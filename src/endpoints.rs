@@ -0,0 +1,134 @@
+//! Metadata about every endpoint of the Terminal API
+//!
+//! Generated from the OpenAPI client at build time, for tooling that needs to
+//! know about endpoints without calling them: access policies, metrics labels,
+//! documentation.
+//!
+//! ```
+//! use terminal::endpoints::{self, HttpMethod};
+//!
+//! let delete_card = endpoints::find("delete_card_by_id").unwrap();
+//! assert_eq!(delete_card.method, HttpMethod::Delete);
+//! assert_eq!(delete_card.path, "/card/{id}");
+//!
+//! let mutating: Vec<_> = endpoints::ALL.iter().filter(|e| e.mutates_state()).collect();
+//! # assert!(!mutating.is_empty());
+//! ```
+
+use std::fmt;
+
+/// The HTTP method of an endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HttpMethod {
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+}
+
+impl HttpMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Get => "GET",
+            Self::Post => "POST",
+            Self::Put => "PUT",
+            Self::Patch => "PATCH",
+            Self::Delete => "DELETE",
+        }
+    }
+
+    /// Whether requests with this method are read-only
+    pub fn is_safe(&self) -> bool {
+        matches!(self, Self::Get)
+    }
+}
+
+impl fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Describes one endpoint of the Terminal API
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EndpointInfo {
+    /// The [`Client`](crate::Client) method, e.g. `list_addresses`
+    pub name: &'static str,
+    /// The function in `openapi::apis` the method wraps, e.g. `get_address`
+    pub operation: &'static str,
+    /// The `openapi::apis` module the function is in, e.g. `address_api`
+    pub module: &'static str,
+    pub method: HttpMethod,
+    /// The path template relative to the base URL, e.g. `/address/{id}`
+    pub path: &'static str,
+    /// The request body model, e.g. `PostAddressRequest`
+    pub body: Option<&'static str>,
+    /// The HTTP statuses the endpoint documents an error response for
+    pub error_statuses: &'static [u16],
+    /// Whether the request is sent with the client's bearer token
+    pub requires_auth: bool,
+    /// The first line of the endpoint's documentation
+    pub summary: &'static str,
+}
+
+impl EndpointInfo {
+    /// Whether calling the endpoint can change anything on the server
+    pub fn mutates_state(&self) -> bool {
+        !self.method.is_safe()
+    }
+
+    /// The names of the path parameters, e.g. `["id"]` for `/address/{id}`
+    pub fn path_params(&self) -> impl Iterator<Item = &'static str> {
+        self.path
+            .split('/')
+            .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+    }
+}
+
+/// Finds an endpoint by its [`Client`](crate::Client) method name
+/// (`list_addresses`) or by the name of the function it wraps (`get_address`)
+pub fn find(name: &str) -> Option<&'static EndpointInfo> {
+    ALL.iter()
+        .find(|endpoint| endpoint.name == name || endpoint.operation == name)
+}
+
+// generated table from build.rs
+include!(concat!(env!("OUT_DIR"), "/endpoints_gen.rs"));
+
+// `all` enables the groups `test_find` needs, so this covers both tests
+#[cfg(all(test, feature = "address", feature = "cart", feature = "product"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "all")]
+    fn test_every_client_method_is_listed() {
+        assert_eq!(ALL.len(), 36);
+        assert!(ALL.iter().all(|endpoint| endpoint.path.starts_with('/')));
+        assert!(ALL.iter().all(|endpoint| !endpoint.summary.is_empty()));
+    }
+
+    #[test]
+    #[cfg(all(feature = "address", feature = "cart", feature = "product"))]
+    fn test_find() {
+        let endpoint = find("list_addresses").unwrap();
+        assert_eq!(find("get_address"), Some(endpoint));
+        assert_eq!(endpoint.method, HttpMethod::Get);
+        assert_eq!(endpoint.path, "/address");
+        assert!(!endpoint.mutates_state());
+        assert!(endpoint.requires_auth);
+
+        let endpoint = find("set_cart_item").unwrap();
+        assert_eq!(endpoint.body, Some("PutCartItemRequest"));
+        assert!(endpoint.mutates_state());
+        assert!(endpoint.error_statuses.contains(&400));
+
+        let endpoint = find("get_product_by_id").unwrap();
+        assert!(!endpoint.requires_auth);
+        assert_eq!(endpoint.path_params().collect::<Vec<_>>(), vec!["id"]);
+
+        assert!(find("get_adress").is_none());
+    }
+}
@@ -1,6 +1,21 @@
+use std::future::Future;
+use std::pin::Pin;
+
+// unused when every API group feature is disabled
+#[allow(unused_imports)]
 use openapi::apis;
 pub use openapi::apis::configuration::Configuration as Config;
-pub use openapi::models;
+
+#[cfg(any(feature = "address", feature = "order", feature = "subscription"))]
+pub mod builders;
+#[cfg(feature = "card")]
+pub mod cards;
+pub mod endpoints;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod models;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 /// Represents a client for the [Terminal API]
 /// 
@@ -17,5 +32,27 @@ impl Default for Client {
     }
 }
 
+impl Client {
+    /// Returns a view of this client whose methods return the full response
+    /// models (e.g. `GetCart200Response` instead of `Cart`)
+    pub fn raw(&self) -> RawClient<'_> {
+        RawClient { client: self }
+    }
+}
+
+/// A view of a [`Client`] whose methods return the full response models
+/// defined by the API rather than just their `data`
+///
+/// Useful if the API starts sending fields next to `data` before this SDK
+/// knows about them.
+#[derive(Debug, Clone, Copy)]
+pub struct RawClient<'a> {
+    #[allow(dead_code)]
+    client: &'a Client,
+}
+
+/// The future returned by [`TerminalApi`] methods
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
 // generated client from build.rs
 include!(concat!(env!("OUT_DIR"), "/api_methods_gen.rs"));
@@ -0,0 +1,267 @@
+//! A programmable [`TerminalApi`] implementation for tests
+//!
+//! [`MockClient`] has an `expect_{method}()` for every client method, which
+//! sets what the method returns and how many times it should be called. Calls
+//! are recorded with their arguments, owned (e.g. `String` for a `&str`).
+//!
+//! ```
+//! # #[cfg(all(feature = "cart", feature = "product"))]
+//! # mod example {
+//! use terminal::TerminalApi;
+//! use terminal::mock::MockClient;
+//! use terminal::models::{Cart, ProductId};
+//!
+//! async fn cart_size(api: &dyn TerminalApi) -> usize {
+//!     api.get_cart().await.map(|cart| cart.items.len()).unwrap_or(0)
+//! }
+//!
+//! # async fn example() {
+//! let mut mock = MockClient::new();
+//! mock.expect_get_cart().return_value(Cart::default()).times(1);
+//! mock.expect_get_product_by_id().return_error(404, None);
+//!
+//! assert_eq!(cart_size(&mock).await, 0);
+//! let id = ProductId::from("prd_XXXXXXXXXXXXXXXXXXXXXXXXX");
+//! assert!(mock.get_product_by_id(&id).await.is_err());
+//! assert_eq!(mock.expect_get_product_by_id().calls(), vec![(id,)]);
+//! # }
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::{Mutex, PoisonError};
+
+use openapi::apis;
+
+use crate::TerminalApi;
+// unused when every API group feature is disabled
+#[allow(unused_imports)]
+use crate::{BoxFuture, models};
+
+type Responder<A, T, E> = Box<dyn FnMut(&A) -> Result<T, apis::Error<E>> + Send>;
+
+/// The programmed behavior of one [`MockClient`] method, and its recorded calls
+///
+/// `A` is a tuple of the method's arguments, `T` what it returns on success
+/// and `E` the typed error in its `apis::Error<E>`.
+pub struct Expectation<A, T, E> {
+    name: &'static str,
+    state: Mutex<State<A, T, E>>,
+}
+
+struct State<A, T, E> {
+    respond: Option<Responder<A, T, E>>,
+    times: Option<usize>,
+    calls: Vec<A>,
+}
+
+impl<A, T, E> Expectation<A, T, E> {
+    fn state(&self) -> std::sync::MutexGuard<'_, State<A, T, E>> {
+        // a panicking responder shouldn't hide the calls made so far
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Responds to every call with the result of `respond`, which gets the call's arguments
+    pub fn returning(
+        &mut self,
+        respond: impl FnMut(&A) -> Result<T, apis::Error<E>> + Send + 'static,
+    ) -> &mut Self {
+        self.state().respond = Some(Box::new(respond));
+        self
+    }
+
+    /// Responds to every call with `Ok(value)`
+    pub fn return_value(&mut self, value: T) -> &mut Self
+    where
+        T: Clone + Send + 'static,
+    {
+        self.returning(move |_| Ok(value.clone()))
+    }
+
+    /// Responds to every call with an `Error::ResponseError` with the given
+    /// status and typed error body. Its `content` is empty
+    ///
+    /// # Panics
+    ///
+    /// If `status` isn't a valid HTTP status code
+    pub fn return_error(&mut self, status: u16, entity: Option<E>) -> &mut Self
+    where
+        E: Clone + Send + 'static,
+    {
+        let status = reqwest::StatusCode::from_u16(status).expect("invalid HTTP status code");
+        self.returning(move |_| {
+            Err(apis::Error::ResponseError(apis::ResponseContent {
+                status,
+                content: String::new(),
+                entity: entity.clone(),
+            }))
+        })
+    }
+
+    /// Expects exactly `times` calls: one more panics, and fewer panics when
+    /// the mock is verified
+    pub fn times(&mut self, times: usize) -> &mut Self {
+        self.state().times = Some(times);
+        self
+    }
+
+    /// Expects no calls
+    pub fn never(&mut self) -> &mut Self {
+        self.times(0)
+    }
+
+    /// The arguments of every call so far, oldest first
+    pub fn calls(&self) -> Vec<A>
+    where
+        A: Clone,
+    {
+        self.state().calls.clone()
+    }
+
+    /// The number of calls so far
+    pub fn call_count(&self) -> usize {
+        self.state().calls.len()
+    }
+}
+
+// used by the generated `MockClient` methods, of which there are none when
+// every API group feature is disabled
+#[allow(dead_code)]
+impl<A, T, E> Expectation<A, T, E> {
+    fn new(name: &'static str) -> Self {
+        Expectation {
+            name,
+            state: Mutex::new(State {
+                respond: None,
+                times: None,
+                calls: Vec::new(),
+            }),
+        }
+    }
+
+    fn call(&self, args: A) -> Result<T, apis::Error<E>> {
+        let mut state = self.state();
+        if let Some(times) = state.times {
+            assert!(
+                state.calls.len() < times,
+                "`{}` expected {} call(s), but was called again",
+                self.name,
+                times
+            );
+        }
+
+        let Some(respond) = state.respond.as_mut() else {
+            panic!(
+                "unexpected call to `{}`, program it with `expect_{}()`",
+                self.name, self.name
+            );
+        };
+        let result = respond(&args);
+        state.calls.push(args);
+        result
+    }
+
+    fn verify(&self) {
+        let state = self.state();
+        if let Some(times) = state.times {
+            assert_eq!(
+                state.calls.len(),
+                times,
+                "`{}` expected {} call(s), but was called {} time(s)",
+                self.name,
+                times,
+                state.calls.len()
+            );
+        }
+    }
+}
+
+impl<A: fmt::Debug, T, E> fmt::Debug for Expectation<A, T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state();
+        f.debug_struct("Expectation")
+            .field("name", &self.name)
+            .field("programmed", &state.respond.is_some())
+            .field("times", &state.times)
+            .field("calls", &state.calls)
+            .finish()
+    }
+}
+
+// generated mock client from build.rs
+include!(concat!(env!("OUT_DIR"), "/mock_gen.rs"));
+
+#[cfg(all(test, any(feature = "card", feature = "cart")))]
+mod tests {
+    use super::*;
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    /// the mock's futures are always ready, so one poll resolves them
+    fn resolve<F: Future>(future: F) -> F::Output {
+        match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("mock future wasn't ready"),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "cart")]
+    fn test_mock_client() {
+        let mut mock = MockClient::new();
+        mock.expect_get_cart()
+            .return_value(models::Cart::default())
+            .times(2);
+        // apis::Error is large, but that's the generated code's choice
+        #[allow(clippy::result_large_err)]
+        mock.expect_set_cart_item().returning(|(request,)| {
+            Ok(models::Cart {
+                card_id: Some(format!("crd_{}", request.quantity).into()),
+                ..Default::default()
+            })
+        });
+
+        assert!(resolve(mock.get_cart()).is_ok());
+        assert!(resolve(mock.get_cart()).is_ok());
+
+        let request = models::PutCartItemRequest::new("var_XXXXXXXXXXXXXXXXXXXXXXXXX".into(), 3);
+        let cart = resolve(mock.set_cart_item(request)).unwrap();
+        assert_eq!(cart.card_id, Some("crd_3".into()));
+        assert_eq!(mock.expect_set_cart_item().calls()[0].0.quantity, 3);
+
+        mock.verify();
+    }
+
+    #[test]
+    #[cfg(feature = "card")]
+    fn test_mock_client_errors() {
+        let mut mock = MockClient::new();
+        mock.expect_get_card_by_id().return_error(404, None);
+
+        let id = models::CardId::from("crd_XXXXXXXXXXXXXXXXXXXXXXXXX");
+        match resolve(mock.get_card_by_id(&id)) {
+            Err(apis::Error::ResponseError(response)) => assert_eq!(response.status, 404),
+            other => panic!("expected a 404, got {:?}", other.map(|_| ())),
+        }
+        assert_eq!(mock.expect_get_card_by_id().calls(), vec![(id,)]);
+    }
+
+    #[test]
+    #[cfg(feature = "cart")]
+    #[should_panic(expected = "unexpected call to `get_cart`")]
+    fn test_mock_client_unexpected_call() {
+        let mock = MockClient::new();
+        let _ = resolve(mock.get_cart());
+    }
+
+    #[test]
+    #[cfg(feature = "cart")]
+    #[should_panic(expected = "`get_cart` expected 1 call(s), but was called 0 time(s)")]
+    fn test_mock_client_verify() {
+        let mut mock = MockClient::new();
+        mock.expect_get_cart()
+            .return_value(models::Cart::default())
+            .times(1);
+    }
+}
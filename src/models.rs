@@ -0,0 +1,45 @@
+//! Models used by the [Terminal API]
+//!
+//! These are the models from the generated `openapi` crate, plus clearer names
+//! for response types whose generated names are an accident of codegen.
+//!
+//! [Terminal API]: https://www.terminal.shop/api
+
+pub use openapi::models::*;
+
+/// Acknowledgement returned by endpoints that have no other response data,
+/// such as `delete_cart`, `put_cart_address` or `post_subscription`
+#[cfg(any(
+    feature = "address",
+    feature = "app",
+    feature = "card",
+    feature = "cart",
+    feature = "miscellaneous",
+    feature = "subscription",
+    feature = "token"
+))]
+pub use openapi::models::DeleteAddressById200Response as Ack;
+
+/// The status inside an [`Ack`], which is always `ok`
+#[cfg(any(
+    feature = "address",
+    feature = "app",
+    feature = "card",
+    feature = "cart",
+    feature = "miscellaneous",
+    feature = "subscription",
+    feature = "token"
+))]
+pub use openapi::models::delete_address_by_id_200_response::Data as AckStatus;
+
+#[cfg(any(
+    feature = "address",
+    feature = "app",
+    feature = "card",
+    feature = "cart",
+    feature = "miscellaneous",
+    feature = "subscription",
+    feature = "token"
+))]
+#[deprecated(note = "renamed to `Ack`")]
+pub type DeleteAddressById200Response = Ack;
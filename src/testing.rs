@@ -0,0 +1,42 @@
+//! Helpers for integration-testing code that talks to the Terminal API
+//!
+//! [`MockServer`] is a local, in-memory stand-in for `api.dev.terminal.shop`.
+//! It serves every endpoint over HTTP, keeps the state a real account would
+//! have (cart, addresses, cards, orders, ...) and enforces the same business
+//! rules, so a [`Client`](crate::Client) pointed at it behaves like one talking
+//! to the real API.
+//!
+//! A [`Cassette`] records the traffic of a client talking to the real API to
+//! a file, and replays it in later runs, e.g. in CI.
+//!
+//! [`fixtures`] builds valid models to assert against or seed a
+//! [`MockServer`] with.
+//!
+//! ```
+//! use terminal::testing::MockServer;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> std::io::Result<()> {
+//! let server = MockServer::start().await?;
+//! let client = server.client();
+//!
+//! let products = client.list_products().await.unwrap();
+//! let cart = client
+//!     .set_cart_item(terminal::models::PutCartItemRequest::new(products[0].variants[0].id.clone(), 2))
+//!     .await
+//!     .unwrap();
+//! assert_eq!(cart.subtotal, products[0].variants[0].price * 2);
+//!
+//! // checking out needs a shipping address and a card
+//! assert!(client.checkout().await.is_err());
+//! # Ok(())
+//! # }
+//! ```
+
+mod cassette;
+pub mod fixtures;
+mod server;
+mod state;
+
+pub use cassette::{Cassette, CassetteClient, Mode};
+pub use server::{MockServer, MockServerBuilder};
@@ -0,0 +1,643 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+
+use http_body_util::Full;
+use hyper::Response;
+use hyper::body::Bytes;
+use hyper::header::{
+    AUTHORIZATION, CONNECTION, CONTENT_LENGTH, COOKIE, HOST, HeaderMap, HeaderName, HeaderValue,
+    PROXY_AUTHORIZATION, SET_COOKIE, TRANSFER_ENCODING,
+};
+use hyper::http::request::Parts;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+use super::server::{error_response, serve};
+use super::state::ApiError;
+use crate::models::error_response::Type as ErrorType;
+use crate::{Client, Config};
+
+/// Whether a [`Cassette`] talks to the API or plays back a recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Forward requests to the API and save every interaction to the file,
+    /// replacing what it had
+    Record,
+    /// Answer requests from the file without touching the network
+    Replay,
+}
+
+/// HTTP traffic recorded to a file once and replayed in later test runs
+///
+/// A cassette is a proxy on `127.0.0.1` in front of the API, and
+/// [`Cassette::client`] returns a [`Client`] pointed at it. When recording,
+/// each request is forwarded and the file is rewritten after every response,
+/// with credentials (`Authorization`, `Cookie`, `Set-Cookie`) left out. When
+/// replaying, each request is answered with the first recorded interaction
+/// with the same method, path and body (compared as JSON, so key order doesn't
+/// matter) that hasn't been played yet. A request without one gets a 500
+/// `unmatched_request` [`ErrorResponse`](crate::models::ErrorResponse) saying
+/// so, and the [`CassetteClient`] panics when it's dropped, failing the test
+/// that made it.
+///
+/// Cassettes are YAML if the path ends in `.yaml` or `.yml`, and JSON
+/// otherwise. The proxy runs on its own thread, so it works with any async
+/// runtime, and stops when the [`CassetteClient`] is dropped.
+///
+/// ```no_run
+/// use terminal::Client;
+/// use terminal::testing::Mode;
+///
+/// # async fn example() -> std::io::Result<()> {
+/// let client = Client::with_cassette("tests/fixtures/checkout.json", Mode::Replay)?;
+/// let products = client.list_products().await.unwrap();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: Mode,
+    upstream: String,
+}
+
+impl Cassette {
+    /// A cassette stored at `path`, recording from the default API base URL
+    pub fn new(path: impl Into<PathBuf>, mode: Mode) -> Self {
+        Cassette {
+            path: path.into(),
+            mode,
+            upstream: Config::default().base_path,
+        }
+    }
+
+    /// Records from this base URL instead, e.g. a [`MockServer`](super::MockServer)'s
+    pub fn upstream(mut self, base_url: impl Into<String>) -> Self {
+        self.upstream = base_url.into();
+        self
+    }
+
+    /// Starts the proxy and returns a client sending its requests through it
+    ///
+    /// When recording, set the client's `bearer_access_token` as usual; it's
+    /// forwarded but not saved.
+    ///
+    /// # Errors
+    ///
+    /// If the cassette can't be read (replay) or created (record), or no
+    /// local port is free
+    pub fn client(self) -> io::Result<CassetteClient> {
+        let player = match self.mode {
+            Mode::Record => {
+                let tape = Tape::default();
+                tape.save(&self.path)?;
+                Player::Record {
+                    upstream: self.upstream.trim_end_matches('/').to_owned(),
+                    client: reqwest::Client::new(),
+                    tape: Mutex::new(tape),
+                }
+            }
+            Mode::Replay => {
+                let interactions = Tape::load(&self.path)?
+                    .interactions
+                    .into_iter()
+                    .map(|interaction| (interaction, false))
+                    .collect();
+                Player::Replay {
+                    interactions: Mutex::new(interactions),
+                }
+            }
+        };
+        let misses = Arc::new(Mutex::new(Vec::new()));
+        let proxy = Arc::new(Proxy {
+            path: self.path,
+            player,
+            misses: Arc::clone(&misses),
+        });
+
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let (shutdown, stopped) = oneshot::channel::<()>();
+        let thread = thread::Builder::new()
+            .name("terminal-cassette".to_owned())
+            .spawn(move || {
+                runtime.block_on(async move {
+                    let Ok(listener) = TcpListener::from_std(listener) else {
+                        return;
+                    };
+                    tokio::spawn(serve(listener, move |parts, body| {
+                        let proxy = Arc::clone(&proxy);
+                        async move { proxy.respond(parts, body).await }
+                    }));
+                    // resolves when the sender is dropped with the client
+                    let _ = stopped.await;
+                });
+                // dropping the runtime cancels the server and its connections
+            })?;
+
+        Ok(CassetteClient {
+            client: Client::new(Config {
+                base_path: format!("http://{}", addr),
+                ..Config::default()
+            }),
+            shutdown: Some(shutdown),
+            thread: Some(thread),
+            misses,
+        })
+    }
+}
+
+impl Client {
+    /// Returns a client whose requests go through the cassette at `path`,
+    /// recorded or replayed depending on `mode`. See [`Cassette`]
+    pub fn with_cassette(path: impl Into<PathBuf>, mode: Mode) -> io::Result<CassetteClient> {
+        Cassette::new(path, mode).client()
+    }
+}
+
+/// A [`Client`] sending its requests through a [`Cassette`]
+///
+/// Derefs to the [`Client`]. Dropping it stops the cassette's proxy and frees
+/// its port; a recording has been saved after every response already.
+///
+/// # Panics
+///
+/// On drop, if a replayed request had no recorded interaction
+#[derive(Debug)]
+pub struct CassetteClient {
+    client: Client,
+    shutdown: Option<oneshot::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+    /// the replayed requests that matched no interaction
+    misses: Arc<Mutex<Vec<String>>>,
+}
+
+impl Deref for CassetteClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        &self.client
+    }
+}
+
+impl DerefMut for CassetteClient {
+    fn deref_mut(&mut self) -> &mut Client {
+        &mut self.client
+    }
+}
+
+impl Drop for CassetteClient {
+    fn drop(&mut self) {
+        drop(self.shutdown.take());
+        if let Some(thread) = self.thread.take() {
+            // the proxy only panics if a responder does, which the client has seen already
+            let _ = thread.join();
+        }
+
+        let misses = self.misses.lock().unwrap_or_else(PoisonError::into_inner);
+        // a second panic while unwinding would abort and hide the first
+        if !misses.is_empty() && !thread::panicking() {
+            panic!("unmatched cassette requests:\n{}", misses.join("\n"));
+        }
+    }
+}
+
+/// The file format of a cassette
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Tape {
+    interactions: Vec<Interaction>,
+}
+
+impl Tape {
+    fn load(path: &Path) -> io::Result<Tape> {
+        let text = fs::read_to_string(path)?;
+        let tape = if is_yaml(path) {
+            serde_yaml::from_str(&text).map_err(|err| err.to_string())
+        } else {
+            serde_json::from_str(&text).map_err(|err| err.to_string())
+        };
+        tape.map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} isn't a cassette: {}", path.display(), err),
+            )
+        })
+    }
+
+    fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(folder) = path
+            .parent()
+            .filter(|folder| !folder.as_os_str().is_empty())
+        {
+            fs::create_dir_all(folder)?;
+        }
+        let text = if is_yaml(path) {
+            serde_yaml::to_string(self).map_err(io::Error::other)?
+        } else {
+            serde_json::to_string_pretty(self).map_err(io::Error::other)? + "\n"
+        };
+        fs::write(path, text)
+    }
+}
+
+/// Whether the cassette at `path` is YAML rather than JSON, by its extension
+fn is_yaml(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "yaml" || extension == "yml")
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    /// the path and query, relative to the base URL
+    path: String,
+    headers: BTreeMap<String, String>,
+    #[serde(flatten)]
+    body: Body,
+}
+
+impl RecordedRequest {
+    fn new(parts: &Parts, body: &[u8]) -> Self {
+        RecordedRequest {
+            method: parts.method.to_string(),
+            path: parts
+                .uri
+                .path_and_query()
+                .map_or_else(|| parts.uri.path().to_owned(), ToString::to_string),
+            headers: recorded_headers(&parts.headers),
+            body: Body::new(body),
+        }
+    }
+
+    /// Whether `self` was recorded for a request like `other`. Headers are
+    /// ignored, they vary with the client version
+    fn matches(&self, other: &RecordedRequest) -> bool {
+        self.method == other.method && self.path == other.path && self.body == other.body
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    headers: BTreeMap<String, String>,
+    #[serde(flatten)]
+    body: Body,
+}
+
+impl RecordedResponse {
+    fn to_response(&self) -> Response<Full<Bytes>> {
+        let mut response = Response::new(Full::new(self.body.to_bytes()));
+        *response.status_mut() = hyper::StatusCode::from_u16(self.status)
+            .unwrap_or(hyper::StatusCode::INTERNAL_SERVER_ERROR);
+        for (name, value) in &self.headers {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::try_from(name.as_str()),
+                HeaderValue::from_str(value),
+            ) {
+                response.headers_mut().insert(name, value);
+            }
+        }
+        response
+    }
+}
+
+/// A request or response body: JSON is stored as JSON so cassettes are
+/// readable and diff well, anything else as text
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct Body {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body_text: Option<String>,
+}
+
+impl Body {
+    fn new(bytes: &[u8]) -> Self {
+        if bytes.is_empty() {
+            return Body::default();
+        }
+        match serde_json::from_slice(bytes) {
+            Ok(json) => Body {
+                body: Some(json),
+                body_text: None,
+            },
+            Err(_) => Body {
+                body: None,
+                body_text: Some(String::from_utf8_lossy(bytes).into_owned()),
+            },
+        }
+    }
+
+    fn to_bytes(&self) -> Bytes {
+        match (&self.body, &self.body_text) {
+            (Some(json), _) => Bytes::from(json.to_string()),
+            (None, Some(text)) => Bytes::from(text.clone()),
+            (None, None) => Bytes::new(),
+        }
+    }
+}
+
+/// The headers worth saving, for requests and responses alike: not
+/// credentials or session cookies, and not the ones describing the connection
+/// rather than the message
+fn recorded_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    let mut recorded = BTreeMap::new();
+    for (name, value) in headers {
+        if [
+            AUTHORIZATION,
+            CONNECTION,
+            CONTENT_LENGTH,
+            COOKIE,
+            HOST,
+            PROXY_AUTHORIZATION,
+            SET_COOKIE,
+            TRANSFER_ENCODING,
+        ]
+        .contains(name)
+        {
+            continue;
+        }
+        let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+        recorded
+            .entry(name.to_string())
+            .and_modify(|values: &mut String| {
+                values.push_str(", ");
+                values.push_str(&value);
+            })
+            .or_insert(value);
+    }
+    recorded
+}
+
+/// What the proxy thread of a [`Cassette`] shares between connections
+struct Proxy {
+    path: PathBuf,
+    player: Player,
+    /// shared with the [`CassetteClient`], which reports them when dropped
+    misses: Arc<Mutex<Vec<String>>>,
+}
+
+enum Player {
+    Record {
+        upstream: String,
+        client: reqwest::Client,
+        tape: Mutex<Tape>,
+    },
+    Replay {
+        /// every recorded interaction, and whether it's been played
+        interactions: Mutex<Vec<(Interaction, bool)>>,
+    },
+}
+
+impl Proxy {
+    async fn respond(&self, parts: Parts, body: Bytes) -> Response<Full<Bytes>> {
+        let request = RecordedRequest::new(&parts, &body);
+        match &self.player {
+            Player::Record {
+                upstream,
+                client,
+                tape,
+            } => {
+                let url = format!("{}{}", upstream, request.path);
+                let response = match forward(client, &url, parts, body).await {
+                    Ok(response) => response,
+                    Err(err) => {
+                        return error_response(ApiError::new(
+                            ErrorType::Internal,
+                            "upstream_error",
+                            format!("couldn't record {} {}: {}", request.method, url, err),
+                        ));
+                    }
+                };
+
+                let mut tape = tape.lock().unwrap_or_else(PoisonError::into_inner);
+                tape.interactions.push(Interaction {
+                    request,
+                    response: response.clone(),
+                });
+                if let Err(err) = tape.save(&self.path) {
+                    return error_response(ApiError::new(
+                        ErrorType::Internal,
+                        "cassette_not_saved",
+                        format!("couldn't save {}: {}", self.path.display(), err),
+                    ));
+                }
+                response.to_response()
+            }
+            Player::Replay { interactions } => {
+                let mut interactions = interactions.lock().unwrap_or_else(PoisonError::into_inner);
+                let unplayed = interactions
+                    .iter_mut()
+                    .find(|(interaction, played)| !played && interaction.request.matches(&request));
+                match unplayed {
+                    Some((interaction, played)) => {
+                        *played = true;
+                        interaction.response.to_response()
+                    }
+                    None => {
+                        let message = format!(
+                            "{} has no unplayed interaction for {} {} with this body",
+                            self.path.display(),
+                            request.method,
+                            request.path
+                        );
+                        self.misses
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .push(message.clone());
+                        error_response(ApiError::new(
+                            ErrorType::Internal,
+                            "unmatched_request",
+                            message,
+                        ))
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Sends a request on to `url`, with the headers it came with
+async fn forward(
+    client: &reqwest::Client,
+    url: &str,
+    parts: Parts,
+    body: Bytes,
+) -> Result<RecordedResponse, reqwest::Error> {
+    let mut headers = parts.headers;
+    headers.remove(HOST);
+    let response = client
+        .request(parts.method, url)
+        .headers(headers)
+        .body(body)
+        .send()
+        .await?;
+
+    let status = response.status().as_u16();
+    let headers = recorded_headers(response.headers());
+    let body = response.bytes().await?;
+    Ok(RecordedResponse {
+        status,
+        headers,
+        body: Body::new(&body),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apis::Error;
+    use crate::models::PutCartItemRequest;
+    use crate::testing::MockServer;
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let path = std::env::temp_dir()
+            .join(format!("terminal-{}", std::process::id()))
+            .join("checkout.json");
+
+        let server = MockServer::start().await.unwrap();
+        let mut client = Cassette::new(&path, Mode::Record)
+            .upstream(server.url())
+            .client()
+            .unwrap();
+        client.config.bearer_access_token = Some(server.token().to_owned());
+        let products = client.list_products().await.unwrap();
+        let request = PutCartItemRequest::new(products[0].variants[0].id.clone(), 2);
+        let cart = client.set_cart_item(request.clone()).await.unwrap();
+
+        let recorded = fs::read_to_string(&path).unwrap();
+        assert!(!recorded.contains(server.token()));
+        assert!(!recorded.contains("authorization"));
+        drop(server);
+
+        let client = Client::with_cassette(&path, Mode::Replay).unwrap();
+        assert_eq!(client.list_products().await.unwrap(), products);
+        assert_eq!(client.set_cart_item(request).await.unwrap(), cart);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "has no unplayed interaction for GET /product ")]
+    async fn test_unmatched_request_panics_on_drop() {
+        let path = std::env::temp_dir()
+            .join(format!("terminal-unmatched-{}", std::process::id()))
+            .join("products.json");
+
+        let server = MockServer::start().await.unwrap();
+        let client = Cassette::new(&path, Mode::Record)
+            .upstream(server.url())
+            .client()
+            .unwrap();
+        client.list_products().await.unwrap();
+
+        let client = Client::with_cassette(&path, Mode::Replay).unwrap();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        client.list_products().await.unwrap();
+
+        // each interaction is played once
+        match client.list_products().await {
+            Err(Error::ResponseError(response)) => {
+                assert_eq!(response.status, 500);
+                assert!(response.content.contains("unmatched_request"));
+            }
+            other => panic!("expected a 500, got {:?}", other.map(|_| ())),
+        }
+        drop(client);
+    }
+
+    #[test]
+    fn test_credentials_arent_recorded() {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer secret"));
+        headers.insert(COOKIE, HeaderValue::from_static("session=secret"));
+        headers.append(SET_COOKIE, HeaderValue::from_static("session=secret"));
+        headers.append(SET_COOKIE, HeaderValue::from_static("csrf=secret"));
+        headers.insert("x-request-id", HeaderValue::from_static("req_1"));
+
+        let recorded = recorded_headers(&headers);
+        assert_eq!(
+            recorded.into_iter().collect::<Vec<_>>(),
+            vec![("x-request-id".to_owned(), "req_1".to_owned())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_yaml_cassettes() {
+        let path = std::env::temp_dir()
+            .join(format!("terminal-yaml-{}", std::process::id()))
+            .join("products.yaml");
+
+        let server = MockServer::start().await.unwrap();
+        let client = Cassette::new(&path, Mode::Record)
+            .upstream(server.url())
+            .client()
+            .unwrap();
+        let products = client.list_products().await.unwrap();
+        assert!(
+            fs::read_to_string(&path)
+                .unwrap()
+                .starts_with("interactions:")
+        );
+
+        let client = Client::with_cassette(&path, Mode::Replay).unwrap();
+        assert_eq!(client.list_products().await.unwrap(), products);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_dropping_the_client_stops_the_proxy() {
+        let path = std::env::temp_dir()
+            .join(format!("terminal-drop-{}", std::process::id()))
+            .join("empty.json");
+        let client = Cassette::new(&path, Mode::Record).client().unwrap();
+        let addr = client
+            .config
+            .base_path
+            .trim_start_matches("http://")
+            .to_owned();
+        assert!(std::net::TcpStream::connect(&addr).is_ok());
+
+        drop(client);
+        assert!(std::net::TcpStream::connect(&addr).is_err());
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_bodies_match_as_json() {
+        let recorded = Body::new(br#"{"productVariantID": "var_1", "quantity": 2}"#);
+        assert_eq!(
+            recorded,
+            Body::new(br#"{"quantity":2,"productVariantID":"var_1"}"#)
+        );
+        assert_ne!(
+            recorded,
+            Body::new(br#"{"quantity":3,"productVariantID":"var_1"}"#)
+        );
+        assert_eq!(Body::new(b"not json").to_bytes(), "not json");
+        assert_eq!(Body::new(b"").to_bytes(), "");
+    }
+
+    #[test]
+    fn test_replay_needs_a_cassette() {
+        let err = Client::with_cassette("does/not/exist.json", Mode::Replay).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,701 @@
+//! Factories for valid, internally consistent models
+//!
+//! Every factory fills in realistic values: IDs with the API's prefixes,
+//! cart and order totals that add up to their items, addresses that pass
+//! [`PostAddressRequest::normalize`](crate::models::PostAddressRequest::normalize)
+//! and cards that haven't expired. Override what a test cares about and
+//! call `build()`:
+//!
+//! ```
+//! use terminal::testing::fixtures;
+//!
+//! fixtures::seed(42);
+//! let order = fixtures::order().with_items(3).shipped().build();
+//! assert_eq!(order.items.len(), 3);
+//! assert!(order.tracking.number.is_some());
+//!
+//! let cart = fixtures::cart().with_items(2).build();
+//! assert_eq!(cart.subtotal, cart.items.iter().map(|item| item.subtotal).sum());
+//! ```
+//!
+//! Values come from a per-thread sequence that starts from the same seed in
+//! every thread, so a test builds the same fixtures on every run. [`seed`]
+//! restarts it from another seed. Dates, like card expirations and a
+//! subscription's next shipment, are relative to [`REFERENCE_MONTH`] rather
+//! than today, so they don't change from day to day either.
+
+use std::cell::Cell;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::state::{cart_shipping, shipping_cost};
+use crate::models::{
+    self, AddressId, CardBrand, CardId, CountryCode, Money, ProductVariant, SubscriptionSchedule,
+    VariantId,
+};
+
+const DEFAULT_SEED: u64 = 0x7e54_1a11_5e3d_c0de;
+
+thread_local! {
+    static STATE: Cell<u64> = const { Cell::new(DEFAULT_SEED) };
+}
+
+/// Restarts this thread's sequence of fixture values from `seed`
+pub fn seed(seed: u64) {
+    STATE.with(|state| state.set(seed));
+}
+
+/// The next value of this thread's sequence (splitmix64)
+fn next() -> u64 {
+    STATE.with(|state| {
+        let value = state.get().wrapping_add(0x9e37_79b9_7f4a_7c15);
+        state.set(value);
+        let value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        let value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        value ^ (value >> 31)
+    })
+}
+
+/// A number in `low..=high`
+fn between(low: i64, high: i64) -> i64 {
+    low + (next() % (high - low + 1) as u64) as i64
+}
+
+fn pick<T: Clone>(values: &[T]) -> T {
+    values[between(0, values.len() as i64 - 1) as usize].clone()
+}
+
+/// An ID like the API's, e.g. `prd_01J1JFE53306NT180RC4HGPWH8`
+fn id(prefix: &str) -> String {
+    const ALPHABET: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+    let suffix: String = (0..26).map(|_| pick(ALPHABET) as char).collect();
+    format!("{}_{}", prefix, suffix)
+}
+
+fn digits(count: usize) -> String {
+    (0..count).map(|_| pick(b"0123456789") as char).collect()
+}
+
+fn timestamp() -> models::Timestamp {
+    models::Timestamp::new(format!(
+        "2024-{:02}-{:02}T{:02}:{:02}:{:02}.000Z",
+        between(1, 12),
+        between(1, 28),
+        between(0, 23),
+        between(0, 59),
+        between(0, 59),
+    ))
+}
+
+/// A time in the one to three months after `(year, month)`
+fn timestamp_after((year, month): (i32, i32)) -> models::Timestamp {
+    let months = year * 12 + month - 1 + between(1, 3) as i32;
+    models::Timestamp::new(format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.000Z",
+        months / 12,
+        months % 12 + 1,
+        between(1, 28),
+        between(0, 23),
+        between(0, 59),
+        between(0, 59),
+    ))
+}
+
+/// The month fixture dates are relative to, as `(year, month)`. Builders
+/// that generate dates take another with `relative_to`
+pub const REFERENCE_MONTH: (i32, i32) = (2026, 1);
+
+/// The start of `(year, month)` in UTC, to check fixtures against, e.g.
+/// `card.is_expired(fixtures::start_of(fixtures::REFERENCE_MONTH))`. Months
+/// before 1970 are clamped to it
+pub fn start_of((year, month): (i32, i32)) -> SystemTime {
+    let days = models::card_expiration::days_from_civil(i64::from(year), month as u32, 1);
+    UNIX_EPOCH + Duration::from_secs(u64::try_from(days).unwrap_or(0) * 86_400)
+}
+
+const NAMES: &[&str] = &[
+    "Ada Lovelace",
+    "Grace Hopper",
+    "Margaret Hamilton",
+    "Ken Thompson",
+    "Barbara Liskov",
+    "Dennis Ritchie",
+];
+
+/// Starts a [`Product`](models::Product) with one variant
+pub fn product() -> ProductFixture {
+    ProductFixture {
+        name: None,
+        variants: 1,
+        price: None,
+        subscription: None,
+    }
+}
+
+/// Overrides for [`product`]
+#[derive(Debug, Clone)]
+pub struct ProductFixture {
+    name: Option<String>,
+    variants: usize,
+    price: Option<Money>,
+    subscription: Option<models::product::Subscription>,
+}
+
+impl ProductFixture {
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Gives the product `count` variants
+    pub fn with_variants(mut self, count: usize) -> Self {
+        self.variants = count;
+        self
+    }
+
+    /// Prices every variant at `price`
+    pub fn price(mut self, price: Money) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn subscription(mut self, subscription: models::product::Subscription) -> Self {
+        self.subscription = Some(subscription);
+        self
+    }
+
+    pub fn build(self) -> models::Product {
+        let name = self.name.unwrap_or_else(|| {
+            pick(&[
+                "Flow",
+                "Cron",
+                "Segfault",
+                "[object Object]",
+                "404",
+                "Nil Blend",
+            ])
+            .to_owned()
+        });
+        let variants = (0..self.variants)
+            .map(|_| {
+                ProductVariant::new(
+                    id("var").into(),
+                    pick(&["12oz", "Whole Bean", "Ground", "5lb"]).to_owned(),
+                    self.price
+                        .unwrap_or_else(|| Money::from_cents(pick(&[1800, 2200, 2500, 3000]))),
+                )
+            })
+            .collect();
+
+        let mut product = models::Product::new(
+            id("prd").into(),
+            name,
+            pick(&["Cerrado, Brazil", "Kenya, Ethiopia", "Sumatra, Indonesia"]).to_owned(),
+            variants,
+        );
+        product.subscription = self.subscription;
+        product
+    }
+}
+
+/// Starts an [`Address`](models::Address) in the United States
+pub fn address() -> AddressFixture {
+    AddressFixture {
+        country: CountryCode::US,
+    }
+}
+
+/// Overrides for [`address`]
+#[derive(Debug, Clone)]
+pub struct AddressFixture {
+    country: CountryCode,
+}
+
+impl AddressFixture {
+    /// Puts the address in `country`. The US, Canada, the UK, Germany and
+    /// France get a real city, province and postal code; other countries a
+    /// placeholder the API may not accept
+    pub fn country(mut self, country: CountryCode) -> Self {
+        self.country = country;
+        self
+    }
+
+    pub fn build(self) -> models::Address {
+        let (city, province, zip) = match self.country.as_str() {
+            "US" => ("Springfield", Some("IL"), "62704"),
+            "CA" => ("Toronto", Some("ON"), "M5V 3L9"),
+            "GB" => ("London", None, "SW1A 1AA"),
+            "DE" => ("Berlin", None, "10115"),
+            "FR" => ("Paris", None, "75001"),
+            _ => ("Capital City", None, "10000"),
+        };
+        let street = format!(
+            "{} {}",
+            between(1, 999),
+            pick(&["Main St", "Market St", "High St", "Elm St"])
+        );
+
+        let mut address = models::Address::new(
+            id("shp").into(),
+            pick(NAMES).to_owned(),
+            street,
+            city.to_owned(),
+            self.country.into(),
+            zip.to_owned(),
+        );
+        address.province = province.map(str::to_owned);
+        address
+    }
+}
+
+/// Starts a [`Card`](models::Card) that expires two to five years after
+/// [`REFERENCE_MONTH`]
+pub fn card() -> CardFixture {
+    CardFixture {
+        brand: None,
+        expiration: None,
+        expired: false,
+        relative_to: REFERENCE_MONTH,
+    }
+}
+
+/// Overrides for [`card`]
+#[derive(Debug, Clone)]
+pub struct CardFixture {
+    brand: Option<CardBrand>,
+    expiration: Option<(i32, i32)>,
+    expired: bool,
+    relative_to: (i32, i32),
+}
+
+impl CardFixture {
+    pub fn brand(mut self, brand: CardBrand) -> Self {
+        self.brand = Some(brand);
+        self
+    }
+
+    /// Expires at the end of `month` (1-12) of `year`
+    pub fn expires(mut self, year: i32, month: i32) -> Self {
+        self.expiration = Some((year, month));
+        self
+    }
+
+    /// Expired one to three years before the reference month
+    pub fn expired(mut self) -> Self {
+        self.expired = true;
+        self
+    }
+
+    /// Picks the expiration relative to `(year, month)` instead of [`REFERENCE_MONTH`]
+    pub fn relative_to(mut self, year: i32, month: i32) -> Self {
+        self.relative_to = (year, month);
+        self
+    }
+
+    pub fn build(self) -> models::Card {
+        let brand = self.brand.unwrap_or_else(|| {
+            pick(&[
+                CardBrand::Visa,
+                CardBrand::Mastercard,
+                CardBrand::AmericanExpress,
+            ])
+        });
+        let (year, month) = self.expiration.unwrap_or_else(|| {
+            let years = if self.expired {
+                -between(1, 3)
+            } else {
+                between(2, 5)
+            };
+            (self.relative_to.0 + years as i32, between(1, 12) as i32)
+        });
+        models::Card::new(
+            id("crd").into(),
+            brand,
+            models::CardExpiration::new(year, month),
+            digits(4),
+        )
+    }
+}
+
+/// Starts a [`Cart`](models::Cart) with one to three items of new products
+pub fn cart() -> CartFixture {
+    CartFixture {
+        items: Items::Random(between(1, 3) as usize),
+        address: None,
+        card_id: None,
+    }
+}
+
+/// The items of a cart or order
+#[derive(Debug, Clone)]
+enum Items {
+    /// this many items of new products
+    Random(usize),
+    Given(Vec<(ProductVariant, i32)>),
+}
+
+impl Items {
+    fn build(self) -> Vec<(ProductVariant, i32)> {
+        match self {
+            Items::Random(count) => (0..count)
+                .map(|_| {
+                    let product = product().build();
+                    let variant = product.variants[0].clone();
+                    (variant, between(1, 3) as i32)
+                })
+                .collect(),
+            Items::Given(items) => items,
+        }
+    }
+
+    fn push(&mut self, variant: &ProductVariant, quantity: i32) {
+        if let Items::Random(_) = self {
+            *self = Items::Given(Vec::new());
+        }
+        if let Items::Given(items) = self {
+            items.push((variant.clone(), quantity));
+        }
+    }
+}
+
+/// Overrides for [`cart`]
+#[derive(Debug, Clone)]
+pub struct CartFixture {
+    items: Items,
+    address: Option<models::Address>,
+    card_id: Option<CardId>,
+}
+
+impl CartFixture {
+    /// Fills the cart with `count` items of new products
+    pub fn with_items(mut self, count: usize) -> Self {
+        self.items = Items::Random(count);
+        self
+    }
+
+    /// Adds `quantity` of `variant`. The first call replaces the random items
+    pub fn item(mut self, variant: &ProductVariant, quantity: i32) -> Self {
+        self.items.push(variant, quantity);
+        self
+    }
+
+    /// Ships to `address`, which adds shipping to the cart's amount
+    pub fn address(mut self, address: &models::Address) -> Self {
+        self.address = Some(address.clone());
+        self
+    }
+
+    pub fn card(mut self, card_id: impl Into<CardId>) -> Self {
+        self.card_id = Some(card_id.into());
+        self
+    }
+
+    pub fn build(self) -> models::Cart {
+        let items: Vec<_> = self
+            .items
+            .build()
+            .into_iter()
+            .map(|(variant, quantity)| {
+                models::CartItem::new(
+                    id("itm"),
+                    variant.id,
+                    quantity,
+                    variant.price * i64::from(quantity),
+                )
+            })
+            .collect();
+        let subtotal: Money = items.iter().map(|item| item.subtotal).sum();
+
+        let mut amount = models::CartAmount::new(subtotal);
+        let mut cart = models::Cart::new(items, subtotal, amount.clone());
+        if let Some(address) = self.address {
+            let shipping = shipping_cost(&address.country);
+            amount.shipping = Some(shipping);
+            amount.total = Some(subtotal + shipping);
+            cart.amount = Box::new(amount);
+            cart.address_id = Some(address.id);
+            cart.shipping = Some(Box::new(cart_shipping(&address.country)));
+        }
+        cart.card_id = self.card_id;
+        cart
+    }
+}
+
+/// Starts an [`Order`](models::Order) of one to three items, shipping to a
+/// US address and not shipped yet
+pub fn order() -> OrderFixture {
+    OrderFixture {
+        items: Items::Random(between(1, 3) as usize),
+        address: None,
+        shipped: false,
+    }
+}
+
+/// Overrides for [`order`]
+#[derive(Debug, Clone)]
+pub struct OrderFixture {
+    items: Items,
+    address: Option<models::Address>,
+    shipped: bool,
+}
+
+impl OrderFixture {
+    /// Orders `count` items of new products
+    pub fn with_items(mut self, count: usize) -> Self {
+        self.items = Items::Random(count);
+        self
+    }
+
+    /// Adds `quantity` of `variant`. The first call replaces the random items
+    pub fn item(mut self, variant: &ProductVariant, quantity: i32) -> Self {
+        self.items.push(variant, quantity);
+        self
+    }
+
+    pub fn address(mut self, address: &models::Address) -> Self {
+        self.address = Some(address.clone());
+        self
+    }
+
+    /// Gives the order a carrier and tracking number
+    pub fn shipped(mut self) -> Self {
+        self.shipped = true;
+        self
+    }
+
+    pub fn build(self) -> models::Order {
+        let items: Vec<_> = self
+            .items
+            .build()
+            .into_iter()
+            .map(|(variant, quantity)| {
+                let mut item = models::OrderItem::new(
+                    id("itm"),
+                    variant.price * i64::from(quantity),
+                    quantity,
+                );
+                item.description = Some(variant.name);
+                item.product_variant_id = Some(variant.id);
+                item
+            })
+            .collect();
+        let subtotal = items.iter().map(|item| item.amount).sum();
+
+        let address = self.address.unwrap_or_else(|| address().build());
+        let shipping_amount = shipping_cost(&address.country);
+        let mut shipping = models::OrderShipping::new(
+            address.name,
+            address.street1,
+            address.city,
+            address.country,
+            address.zip,
+        );
+        shipping.street2 = address.street2;
+        shipping.province = address.province;
+        shipping.phone = address.phone;
+
+        let mut tracking = models::OrderTracking::new();
+        if self.shipped {
+            let number = digits(22);
+            tracking.url = Some(format!("https://parcelsapp.com/en/tracking/{}", number));
+            tracking.service = cart_shipping(&shipping.country).service;
+            tracking.number = Some(number);
+        }
+
+        let mut order = models::Order::new(
+            id("ord").into(),
+            shipping,
+            models::OrderAmount::new(shipping_amount, subtotal),
+            tracking,
+            items,
+        );
+        order.index = Some(between(1, 100) as i32);
+        order
+    }
+}
+
+/// Starts a [`Subscription`](models::Subscription) to one bag of a new
+/// product, shipped once, one to three months after [`REFERENCE_MONTH`]
+pub fn subscription() -> SubscriptionFixture {
+    SubscriptionFixture {
+        variant_id: None,
+        quantity: 1,
+        address_id: None,
+        card_id: None,
+        schedule: SubscriptionSchedule::Fixed,
+        relative_to: REFERENCE_MONTH,
+    }
+}
+
+/// Overrides for [`subscription`]
+#[derive(Debug, Clone)]
+pub struct SubscriptionFixture {
+    variant_id: Option<VariantId>,
+    quantity: i32,
+    address_id: Option<AddressId>,
+    card_id: Option<CardId>,
+    schedule: SubscriptionSchedule,
+    relative_to: (i32, i32),
+}
+
+impl SubscriptionFixture {
+    pub fn variant(mut self, variant_id: impl Into<VariantId>) -> Self {
+        self.variant_id = Some(variant_id.into());
+        self
+    }
+
+    pub fn quantity(mut self, quantity: i32) -> Self {
+        self.quantity = quantity;
+        self
+    }
+
+    pub fn address(mut self, address_id: impl Into<AddressId>) -> Self {
+        self.address_id = Some(address_id.into());
+        self
+    }
+
+    pub fn card(mut self, card_id: impl Into<CardId>) -> Self {
+        self.card_id = Some(card_id.into());
+        self
+    }
+
+    /// Ships every `interval` weeks
+    pub fn weekly(mut self, interval: i32) -> Self {
+        self.schedule = SubscriptionSchedule::weekly(interval);
+        self
+    }
+
+    /// Schedules the next shipment after `(year, month)` instead of [`REFERENCE_MONTH`]
+    pub fn relative_to(mut self, year: i32, month: i32) -> Self {
+        self.relative_to = (year, month);
+        self
+    }
+
+    pub fn build(self) -> models::Subscription {
+        let variant_id = self
+            .variant_id
+            .unwrap_or_else(|| product().build().variants[0].id.clone());
+        let mut subscription = models::Subscription::new(
+            id("sub").into(),
+            variant_id,
+            self.quantity,
+            self.address_id.unwrap_or_else(|| id("shp").into()),
+            self.card_id.unwrap_or_else(|| id("crd").into()),
+        );
+        subscription.schedule = Some(Box::new(self.schedule));
+        subscription.next = Some(timestamp_after(self.relative_to));
+        subscription
+    }
+}
+
+/// A personal access [`Token`](models::Token)
+pub fn token() -> models::Token {
+    models::Token::new(
+        id("pat").into(),
+        timestamp(),
+        format!("trm_test_{}", id("tok").to_lowercase()),
+    )
+}
+
+/// An OAuth [`App`](models::App) redirecting to `localhost`
+pub fn app() -> models::App {
+    models::App::new(
+        id("cli").into(),
+        pick(&["Dashboard", "Shop Bot", "Roaster CLI"]).to_owned(),
+        "http://localhost:3000/callback".to_owned(),
+        id("sec"),
+    )
+}
+
+/// The [`Profile`](models::Profile) of a user who has set a name and email
+pub fn profile() -> models::Profile {
+    let name = pick(NAMES);
+    let email = format!(
+        "{}@example.com",
+        name.split(' ').next().unwrap_or(name).to_lowercase()
+    );
+    models::Profile::new(models::User::new(
+        id("usr"),
+        Some(name.to_owned()),
+        Some(email),
+        None,
+        id("cus"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded_fixtures_repeat() {
+        seed(7);
+        let first = (product().build(), order().build(), card().build());
+        seed(7);
+        assert_eq!((product().build(), order().build(), card().build()), first);
+        assert_ne!(product().build(), first.0);
+    }
+
+    #[test]
+    fn test_totals_match_items() {
+        let flow = product().price(Money::from_cents(2200)).build();
+        let cart = cart()
+            .item(&flow.variants[0], 2)
+            .address(&address().country(CountryCode::DE).build())
+            .build();
+        assert_eq!(cart.items.len(), 1);
+        assert_eq!(cart.subtotal, Money::from_cents(4400));
+        assert_eq!(cart.amount.total, Some(Money::from_cents(5400)));
+
+        let order = order().with_items(3).build();
+        assert_eq!(order.items.len(), 3);
+        assert_eq!(
+            order.amount.subtotal,
+            order.items.iter().map(|item| item.amount).sum()
+        );
+        assert!(order.tracking.number.is_none());
+        assert!(super::order().shipped().build().tracking.url.is_some());
+    }
+
+    #[test]
+    fn test_models_are_valid() {
+        let now = start_of(REFERENCE_MONTH);
+        assert!(!card().build().is_expired(now));
+        assert!(card().expired().build().is_expired(now));
+        let later = start_of((2040, 6));
+        assert!(!card().relative_to(2040, 6).build().is_expired(later));
+        assert!(
+            card()
+                .relative_to(2040, 6)
+                .expired()
+                .build()
+                .is_expired(later)
+        );
+
+        // timestamps in UTC sort as strings
+        let next = subscription().build().next.unwrap();
+        assert!(next.as_str() > "2026-02", "{}", next);
+        assert!(next.as_str() < "2026-05", "{}", next);
+        let next = subscription().relative_to(2040, 12).build().next.unwrap();
+        assert!(next.as_str().starts_with("2041-0"), "{}", next);
+
+        for country in [
+            CountryCode::US,
+            CountryCode::CA,
+            CountryCode::GB,
+            CountryCode::DE,
+            CountryCode::FR,
+        ] {
+            let address = address().country(country).build();
+            assert_eq!(address.country, country);
+            let mut request = models::PostAddressRequest::new(
+                address.name,
+                address.street1,
+                address.city,
+                country,
+                address.zip,
+            );
+            request.province = address.province;
+            assert!(request.normalize().is_ok(), "{:?}", country);
+        }
+    }
+}
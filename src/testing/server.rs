@@ -0,0 +1,324 @@
+use std::convert::Infallible;
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{AUTHORIZATION, CONTENT_TYPE, HeaderValue};
+use hyper::http::request::Parts;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+use super::state::{ApiError, State};
+use crate::endpoints::{self, EndpointInfo};
+use crate::{Client, Config, models};
+
+/// The bearer token a [`MockServer`] accepts unless built with another one
+const DEFAULT_TOKEN: &str = "trm_test_token";
+
+/// A local Terminal API with in-memory state, for integration tests
+///
+/// It serves every endpoint in [`endpoints::ALL`] on `127.0.0.1`, starting
+/// from an empty account and a small product catalog. Requests are checked
+/// the way the real API checks them: endpoints that need auth want the
+/// server's bearer token, IDs must exist, checking out needs items, an
+/// address and a card, and cart totals always add up. Failures are sent as
+/// [`ErrorResponse`](models::ErrorResponse) bodies with the matching status.
+///
+/// The server runs on the Tokio runtime it was started from, and stops when
+/// dropped.
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    token: String,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Starts a server with the default catalog and token on a free port
+    pub async fn start() -> io::Result<MockServer> {
+        MockServer::builder().start().await
+    }
+
+    /// Returns a builder to change the catalog or token before starting
+    pub fn builder() -> MockServerBuilder {
+        MockServerBuilder::default()
+    }
+
+    /// The base URL to send requests to, e.g. `http://127.0.0.1:49152`
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// The bearer token the server accepts
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// A client configured to talk to this server, authenticated
+    pub fn client(&self) -> Client {
+        Client::new(Config {
+            base_path: self.url(),
+            bearer_access_token: Some(self.token.clone()),
+            ..Config::default()
+        })
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Sets up a [`MockServer`] before starting it
+#[derive(Debug, Clone)]
+pub struct MockServerBuilder {
+    products: Option<Vec<models::Product>>,
+    token: String,
+}
+
+impl Default for MockServerBuilder {
+    fn default() -> Self {
+        MockServerBuilder {
+            products: None,
+            token: DEFAULT_TOKEN.to_owned(),
+        }
+    }
+}
+
+impl MockServerBuilder {
+    /// Serves these products instead of the default catalog
+    pub fn products(mut self, products: Vec<models::Product>) -> Self {
+        self.products = Some(products);
+        self
+    }
+
+    /// Accepts this bearer token instead of the default one
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = token.into();
+        self
+    }
+
+    /// Binds a free local port and starts serving on the current Tokio runtime
+    ///
+    /// # Panics
+    ///
+    /// If called outside a Tokio runtime
+    pub async fn start(self) -> io::Result<MockServer> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let addr = listener.local_addr()?;
+        let server = Arc::new(Server {
+            state: Mutex::new(State::new(self.products)),
+            token: self.token.clone(),
+        });
+
+        let task = tokio::spawn(serve(listener, move |parts, body| {
+            let server = Arc::clone(&server);
+            async move { server.respond(parts, &body) }
+        }));
+
+        Ok(MockServer {
+            addr,
+            token: self.token,
+            task,
+        })
+    }
+}
+
+/// What every connection of a [`MockServer`] shares
+struct Server {
+    state: Mutex<State>,
+    token: String,
+}
+
+impl Server {
+    fn respond(&self, parts: Parts, body: &[u8]) -> Response<Full<Bytes>> {
+        let Some((endpoint, id)) = route(parts.method.as_str(), parts.uri.path()) else {
+            return error_response(ApiError::not_found(format!(
+                "no route for {} {}",
+                parts.method,
+                parts.uri.path()
+            )));
+        };
+
+        let authorized = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| token == self.token);
+        if endpoint.requires_auth && !authorized {
+            return error_response(ApiError::unauthorized());
+        }
+
+        let result = self
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .handle(endpoint.operation, id.as_deref(), body);
+        match result {
+            Ok(data) => json_response(200, &serde_json::json!({ "data": data })),
+            Err(err) => error_response(err),
+        }
+    }
+}
+
+/// Serves HTTP/1 connections from `listener` until it fails, answering every
+/// request with `respond(parts, body)`
+pub(super) async fn serve<F, Fut>(listener: TcpListener, respond: F)
+where
+    F: Fn(Parts, Bytes) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Response<Full<Bytes>>> + Send,
+{
+    while let Ok((stream, _)) = listener.accept().await {
+        let respond = respond.clone();
+        tokio::spawn(async move {
+            let service = service_fn(|request: Request<Incoming>| {
+                let respond = respond.clone();
+                async move {
+                    let (parts, body) = request.into_parts();
+                    let response = match body.collect().await {
+                        Ok(body) => respond(parts, body.to_bytes()).await,
+                        Err(err) => error_response(ApiError::invalid_body(err)),
+                    };
+                    Ok::<_, Infallible>(response)
+                }
+            });
+            // a client hanging up mid-request isn't the test's concern
+            let _ = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await;
+        });
+    }
+}
+
+/// The endpoint a request is for, and its `{id}` path param if it has one
+fn route(method: &str, path: &str) -> Option<(&'static EndpointInfo, Option<String>)> {
+    let segments: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+    endpoints::ALL.iter().find_map(|endpoint| {
+        if endpoint.method.as_str() != method {
+            return None;
+        }
+        let template: Vec<&str> = endpoint.path.split('/').collect();
+        if template.len() != segments.len() {
+            return None;
+        }
+
+        let mut id = None;
+        for (expected, actual) in template.iter().zip(&segments) {
+            if expected.starts_with('{') {
+                id = Some(actual.to_string());
+            } else if expected != actual {
+                return None;
+            }
+        }
+        Some((endpoint, id))
+    })
+}
+
+pub(super) fn error_response(err: ApiError) -> Response<Full<Bytes>> {
+    json_response(err.status, &serde_json::json!(err.body))
+}
+
+pub(super) fn json_response(status: u16, body: &serde_json::Value) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(body.to_string())));
+    *response.status_mut() =
+        hyper::StatusCode::from_u16(status).unwrap_or(hyper::StatusCode::INTERNAL_SERVER_ERROR);
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apis::Error;
+
+    #[test]
+    fn test_route() {
+        let (endpoint, id) = route("GET", "/card/crd_1").unwrap();
+        assert_eq!(endpoint.operation, "get_card_by_id");
+        assert_eq!(id.as_deref(), Some("crd_1"));
+
+        assert_eq!(
+            route("POST", "/card/collect").unwrap().0.operation,
+            "post_card_collect"
+        );
+        assert_eq!(route("GET", "/cart/").unwrap().0.operation, "get_cart");
+        assert!(route("PATCH", "/cart").is_none());
+        assert!(route("GET", "/nope").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_checkout_flow() {
+        let server = MockServer::start().await.unwrap();
+        let client = server.client();
+
+        let products = client.list_products().await.unwrap();
+        let variant = &products[0].variants[0];
+        client
+            .set_cart_item(models::PutCartItemRequest::new(variant.id.clone(), 3))
+            .await
+            .unwrap();
+
+        let mut address = models::PostAddressRequest::new(
+            "Ada Lovelace".to_owned(),
+            "1 Main St".to_owned(),
+            "Springfield".to_owned(),
+            models::CountryCode::US,
+            "62704".to_owned(),
+        );
+        address.province = Some("IL".to_owned());
+        let address_id = client.create_address(address).await.unwrap();
+        let card_id = client
+            .create_card(models::PostCardRequest::new("tok_visa".to_owned()))
+            .await
+            .unwrap();
+        client
+            .set_cart_address(models::PutCartAddressRequest::new(address_id))
+            .await
+            .unwrap();
+
+        match client.checkout().await {
+            Err(Error::ResponseError(response)) => {
+                assert_eq!(response.status, 400);
+                assert!(response.content.contains("missing_card"));
+            }
+            other => panic!("expected a 400, got {:?}", other.map(|_| ())),
+        }
+
+        client
+            .set_cart_card(models::PutCartCardRequest::new(card_id))
+            .await
+            .unwrap();
+        let order = client.checkout().await.unwrap();
+        assert_eq!(order.amount.subtotal, variant.price * 3);
+        assert_eq!(client.get_order_by_id(&order.id).await.unwrap(), order);
+        assert!(client.get_cart().await.unwrap().items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_requires_token() {
+        let server = MockServer::builder().token("secret").start().await.unwrap();
+        let anonymous = Client::new(Config {
+            base_path: server.url(),
+            ..Config::default()
+        });
+
+        // the catalog is public
+        assert!(anonymous.list_products().await.is_ok());
+        match anonymous.get_cart().await {
+            Err(Error::ResponseError(response)) => assert_eq!(response.status, 401),
+            other => panic!("expected a 401, got {:?}", other.map(|_| ())),
+        }
+        assert!(server.client().get_cart().await.is_ok());
+    }
+}
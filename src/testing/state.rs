@@ -0,0 +1,821 @@
+//! The in-memory account behind [`MockServer`](super::MockServer) and the
+//! business rules of each operation
+
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use crate::models::{
+    self, AddressId, AppId, CardId, Country, CountryCode, Money, OrderId, SubscriptionId, TokenId,
+    VariantId, error_response::Type as ErrorType,
+};
+
+/// international orders pay a flat shipping rate, domestic ones ship free
+const INTERNATIONAL_SHIPPING: Money = Money::from_cents(1000);
+
+/// the creation time of every token, so responses are reproducible
+const CREATED: &str = "2024-06-29T19:36:19.000Z";
+
+/// An error response and its HTTP status
+#[derive(Debug)]
+pub(crate) struct ApiError {
+    pub status: u16,
+    pub body: Box<models::ErrorResponse>,
+}
+
+impl ApiError {
+    pub(crate) fn new(r#type: ErrorType, code: &str, message: impl Into<String>) -> Self {
+        let status = match r#type {
+            ErrorType::Authentication => 401,
+            ErrorType::Forbidden => 403,
+            ErrorType::NotFound => 404,
+            ErrorType::RateLimit => 429,
+            ErrorType::Validation => 400,
+            _ => 500,
+        };
+        ApiError {
+            status,
+            body: Box::new(models::ErrorResponse::new(
+                r#type,
+                code.to_owned(),
+                message.into(),
+            )),
+        }
+    }
+
+    fn validation(code: &str, message: impl Into<String>, param: &str) -> Self {
+        let mut error = Self::new(ErrorType::Validation, code, message);
+        error.body.param = Some(param.to_owned());
+        error
+    }
+
+    pub(crate) fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorType::NotFound, "not_found", message)
+    }
+
+    pub(crate) fn invalid_body(err: impl fmt::Display) -> Self {
+        Self::new(
+            ErrorType::Validation,
+            "invalid_body",
+            format!("invalid request body: {}", err),
+        )
+    }
+
+    pub(crate) fn unauthorized() -> Self {
+        Self::new(
+            ErrorType::Authentication,
+            "unauthorized",
+            "missing or invalid bearer token",
+        )
+    }
+}
+
+/// The `data` of a successful response, or the error to send instead
+pub(crate) type Response = Result<Value, ApiError>;
+
+/// Everything the API knows about the current user
+#[derive(Debug)]
+pub(crate) struct State {
+    next_id: u64,
+    products: Vec<models::Product>,
+    profile: models::Profile,
+    cart: models::Cart,
+    addresses: Vec<models::Address>,
+    cards: Vec<models::Card>,
+    subscriptions: Vec<models::Subscription>,
+    orders: Vec<models::Order>,
+    tokens: Vec<models::Token>,
+    apps: Vec<models::App>,
+    emails: Vec<String>,
+}
+
+impl State {
+    /// A new account with `products` in the catalog, or a small coffee
+    /// catalog if `None`
+    pub(crate) fn new(products: Option<Vec<models::Product>>) -> Self {
+        let mut state = State {
+            next_id: 0,
+            products: Vec::new(),
+            profile: models::Profile::default(),
+            cart: models::Cart::default(),
+            addresses: Vec::new(),
+            cards: Vec::new(),
+            subscriptions: Vec::new(),
+            orders: Vec::new(),
+            tokens: Vec::new(),
+            apps: Vec::new(),
+            emails: Vec::new(),
+        };
+        state.profile = models::Profile::new(models::User::new(
+            state.id("usr"),
+            None,
+            None,
+            None,
+            state.id("cus"),
+        ));
+        state.products = products.unwrap_or_else(|| state.catalog());
+        state
+    }
+
+    fn catalog(&mut self) -> Vec<models::Product> {
+        [
+            (
+                "Flow",
+                "Cerrado, Brazil",
+                Some(models::product::Subscription::Allowed),
+            ),
+            (
+                "Cron",
+                "Kenya, Ethiopia",
+                Some(models::product::Subscription::Required),
+            ),
+            ("Segfault", "Sumatra, Indonesia", None),
+        ]
+        .into_iter()
+        .map(|(name, description, subscription)| {
+            let variant = models::ProductVariant::new(
+                self.id("var").into(),
+                "12oz".to_owned(),
+                Money::from_cents(2200),
+            );
+            let mut product = models::Product::new(
+                self.id("prd").into(),
+                name.to_owned(),
+                description.to_owned(),
+                vec![variant],
+            );
+            product.subscription = subscription;
+            product
+        })
+        .collect()
+    }
+
+    /// The next ID with the given prefix, e.g. `crd_00000000000000000000000004`
+    fn id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+        format!("{}_{:026}", prefix, self.next_id)
+    }
+
+    /// Runs the operation an [`EndpointInfo`](crate::endpoints::EndpointInfo)
+    /// names, with the request's `{id}` path param and body
+    pub(crate) fn handle(&mut self, operation: &str, id: Option<&str>, body: &[u8]) -> Response {
+        let id = id.unwrap_or_default();
+        match operation {
+            "get_product" => Ok(json!(self.products)),
+            "get_product_by_id" => self.product(id).map(|product| json!(product)),
+
+            "get_profile" => Ok(json!(self.profile)),
+            "put_profile" => self.put_profile(parse(body)?),
+
+            "get_cart" => Ok(json!(self.cart)),
+            "put_cart_item" => self.put_cart_item(parse(body)?),
+            "put_cart_address" => self.put_cart_address(parse(body)?),
+            "put_cart_card" => self.put_cart_card(parse(body)?),
+            "delete_cart" => {
+                self.cart = models::Cart::default();
+                Ok(ack())
+            }
+            "post_cart_convert" => self.post_cart_convert(),
+
+            "get_address" => Ok(json!(self.addresses)),
+            "get_address_by_id" => find(&self.addresses, id, |a| &a.id, "address"),
+            "post_address" => self.post_address(parse(body)?),
+            "delete_address_by_id" => self.delete_address(id),
+
+            "get_card" => Ok(json!(self.cards)),
+            "get_card_by_id" => find(&self.cards, id, |c| &c.id, "card"),
+            "post_card" => self.post_card(parse(body)?),
+            "post_card_collect" => {
+                let session = self.id("cs_test");
+                Ok(json!({ "url": format!("https://checkout.stripe.com/c/pay/{}", session) }))
+            }
+            "delete_card_by_id" => self.delete_card(id),
+
+            "get_order" => Ok(json!(self.orders)),
+            "get_order_by_id" => find(&self.orders, id, |o| &o.id, "order"),
+            "post_order" => self.post_order(parse(body)?),
+
+            "get_subscription" => Ok(json!(self.subscriptions)),
+            "get_subscription_by_id" => find(&self.subscriptions, id, |s| &s.id, "subscription"),
+            "post_subscription" => self.post_subscription(parse(body)?),
+            "delete_subscription_by_id" => {
+                remove(&mut self.subscriptions, id, |s| &s.id, "subscription")
+            }
+
+            "get_token" => Ok(json!(self.tokens)),
+            "get_token_by_id" => find(&self.tokens, id, |t| &t.id, "token"),
+            "post_token" => {
+                let id = TokenId::from(self.id("pat"));
+                let token = self.id("trm_test");
+                self.tokens.push(models::Token::new(
+                    id.clone(),
+                    CREATED.into(),
+                    token.clone(),
+                ));
+                Ok(json!({ "id": id, "token": token }))
+            }
+            "delete_token_by_id" => remove(&mut self.tokens, id, |t| &t.id, "token"),
+
+            "get_app" => Ok(json!(self.apps)),
+            "get_app_by_id" => find(&self.apps, id, |a| &a.id, "app"),
+            "post_app" => self.post_app(parse(body)?),
+            "delete_app_by_id" => remove(&mut self.apps, id, |a| &a.id, "app"),
+
+            "get_view_init" => Ok(json!(models::GetViewInit200ResponseData::new(
+                self.profile.clone(),
+                self.products.clone(),
+                self.cart.clone(),
+                self.addresses.clone(),
+                self.cards.clone(),
+                self.subscriptions.clone(),
+                self.orders.clone(),
+                self.tokens.clone(),
+                self.apps.clone(),
+                models::Region::Na,
+            ))),
+            "post_email" => {
+                let request: models::PostEmailRequest = parse(body)?;
+                validate_email(&request.email)?;
+                self.emails.push(request.email);
+                Ok(ack())
+            }
+
+            other => Err(ApiError::new(
+                ErrorType::Internal,
+                "not_implemented",
+                format!("`{}` isn't implemented by the mock server", other),
+            )),
+        }
+    }
+
+    fn product(&self, id: &str) -> Result<&models::Product, ApiError> {
+        self.products
+            .iter()
+            .find(|product| product.id == id)
+            .ok_or_else(|| ApiError::not_found(format!("product `{}` not found", id)))
+    }
+
+    /// The variant and the product it belongs to, or a validation error on `param`
+    fn variant(
+        &self,
+        id: &VariantId,
+        param: &str,
+    ) -> Result<(&models::Product, &models::ProductVariant), ApiError> {
+        self.products
+            .iter()
+            .find_map(|product| {
+                let variant = product.variants.iter().find(|variant| &variant.id == id)?;
+                Some((product, variant))
+            })
+            .ok_or_else(|| {
+                ApiError::validation(
+                    "invalid_variant",
+                    format!("product variant `{}` not found", id),
+                    param,
+                )
+            })
+    }
+
+    fn address(&self, id: &AddressId, param: &str) -> Result<&models::Address, ApiError> {
+        self.addresses
+            .iter()
+            .find(|address| &address.id == id)
+            .ok_or_else(|| {
+                ApiError::validation(
+                    "invalid_address",
+                    format!("address `{}` not found", id),
+                    param,
+                )
+            })
+    }
+
+    fn card(&self, id: &CardId, param: &str) -> Result<&models::Card, ApiError> {
+        self.cards
+            .iter()
+            .find(|card| &card.id == id)
+            .ok_or_else(|| {
+                ApiError::validation("invalid_card", format!("card `{}` not found", id), param)
+            })
+    }
+
+    fn put_profile(&mut self, request: models::PutProfileRequest) -> Response {
+        if request.name.trim().is_empty() {
+            return Err(ApiError::validation(
+                "invalid_name",
+                "name can't be empty",
+                "name",
+            ));
+        }
+        validate_email(&request.email)?;
+        self.profile.user.name = Some(request.name);
+        self.profile.user.email = Some(request.email);
+        Ok(json!(self.profile))
+    }
+
+    /// Sets the quantity of a variant in the cart, removing it at 0
+    fn put_cart_item(&mut self, request: models::PutCartItemRequest) -> Response {
+        let (product, variant) = self.variant(&request.product_variant_id, "productVariantID")?;
+        if request.quantity < 0 {
+            return Err(ApiError::validation(
+                "invalid_quantity",
+                "quantity can't be negative",
+                "quantity",
+            ));
+        }
+        if product.subscription == Some(models::product::Subscription::Required) {
+            return Err(ApiError::validation(
+                "subscription_required",
+                format!("`{}` is only sold as a subscription", product.name),
+                "productVariantID",
+            ));
+        }
+        let subtotal = variant.price * i64::from(request.quantity);
+
+        let items = &mut self.cart.items;
+        match items
+            .iter()
+            .position(|item| item.product_variant_id == request.product_variant_id)
+        {
+            Some(index) if request.quantity == 0 => {
+                items.remove(index);
+            }
+            Some(index) => {
+                items[index].quantity = request.quantity;
+                items[index].subtotal = subtotal;
+            }
+            None if request.quantity == 0 => {}
+            None => {
+                let item = models::CartItem::new(
+                    self.id("itm"),
+                    request.product_variant_id,
+                    request.quantity,
+                    subtotal,
+                );
+                self.cart.items.push(item);
+            }
+        }
+
+        self.reprice_cart();
+        Ok(json!(self.cart))
+    }
+
+    fn put_cart_address(&mut self, request: models::PutCartAddressRequest) -> Response {
+        self.address(&request.address_id, "addressID")?;
+        self.cart.address_id = Some(request.address_id);
+        self.reprice_cart();
+        Ok(ack())
+    }
+
+    fn put_cart_card(&mut self, request: models::PutCartCardRequest) -> Response {
+        self.card(&request.card_id, "cardID")?;
+        self.cart.card_id = Some(request.card_id);
+        Ok(ack())
+    }
+
+    /// Keeps the cart's totals equal to the sum of its items, plus shipping
+    /// once there's an address to ship to
+    fn reprice_cart(&mut self) {
+        let subtotal: Money = self.cart.items.iter().map(|item| item.subtotal).sum();
+        let country = self
+            .cart
+            .address_id
+            .as_ref()
+            .and_then(|id| self.address(id, "addressID").ok())
+            .map(|address| address.country.clone());
+
+        self.cart.subtotal = subtotal;
+        *self.cart.amount = models::CartAmount::new(subtotal);
+        self.cart.shipping = None;
+        if let Some(country) = country {
+            let shipping = shipping_cost(&country);
+            self.cart.amount.shipping = Some(shipping);
+            self.cart.amount.total = Some(subtotal + shipping);
+            self.cart.shipping = Some(Box::new(cart_shipping(&country)));
+        }
+    }
+
+    /// Places an order for the cart and empties it
+    fn post_cart_convert(&mut self) -> Response {
+        if self.cart.items.is_empty() {
+            return Err(ApiError::validation(
+                "cart_empty",
+                "the cart has no items",
+                "items",
+            ));
+        }
+        let address_id = self.cart.address_id.clone().ok_or_else(|| {
+            ApiError::validation(
+                "missing_address",
+                "the cart has no shipping address",
+                "addressID",
+            )
+        })?;
+        let card_id = self.cart.card_id.clone().ok_or_else(|| {
+            ApiError::validation("missing_card", "the cart has no card", "cardID")
+        })?;
+
+        let items = self
+            .cart
+            .items
+            .iter()
+            .map(|item| (item.product_variant_id.clone(), item.quantity))
+            .collect();
+        let order = self.place_order(items, &address_id, &card_id)?;
+        self.cart = models::Cart::default();
+        Ok(json!(order))
+    }
+
+    fn post_order(&mut self, request: models::PostOrderRequest) -> Response {
+        self.card(&request.card_id, "cardID")?;
+        let mut items: Vec<_> = request.variants.into_iter().collect();
+        // the request's map has no order
+        items.sort();
+        for (variant_id, quantity) in &items {
+            self.variant(variant_id, "variants")?;
+            if *quantity <= 0 {
+                return Err(ApiError::validation(
+                    "invalid_quantity",
+                    format!("the quantity of `{}` must be positive", variant_id),
+                    "variants",
+                ));
+            }
+        }
+        if items.is_empty() {
+            return Err(ApiError::validation(
+                "invalid_variants",
+                "an order needs at least one variant",
+                "variants",
+            ));
+        }
+
+        let order = self.place_order(items, &request.address_id, &request.card_id)?;
+        Ok(json!(order.id))
+    }
+
+    fn place_order(
+        &mut self,
+        items: Vec<(VariantId, i32)>,
+        address_id: &AddressId,
+        card_id: &CardId,
+    ) -> Result<models::Order, ApiError> {
+        self.card(card_id, "cardID")?;
+        let address = self.address(address_id, "addressID")?.clone();
+
+        let mut order_items = Vec::new();
+        for (variant_id, quantity) in items {
+            let (product, variant) = self.variant(&variant_id, "variants")?;
+            let mut item = models::OrderItem::new(
+                String::new(),
+                variant.price * i64::from(quantity),
+                quantity,
+            );
+            item.description = Some(format!("{} {}", product.name, variant.name));
+            item.product_variant_id = Some(variant_id);
+            item.id = self.id("itm");
+            order_items.push(item);
+        }
+        let subtotal = order_items.iter().map(|item| item.amount).sum();
+
+        let shipping_amount = shipping_cost(&address.country);
+        let mut shipping = models::OrderShipping::new(
+            address.name,
+            address.street1,
+            address.city,
+            address.country,
+            address.zip,
+        );
+        shipping.street2 = address.street2;
+        shipping.province = address.province;
+        shipping.phone = address.phone;
+
+        let mut order = models::Order::new(
+            OrderId::from(self.id("ord")),
+            shipping,
+            models::OrderAmount::new(shipping_amount, subtotal),
+            models::OrderTracking::new(),
+            order_items,
+        );
+        order.index = Some(self.orders.len() as i32 + 1);
+        self.orders.push(order.clone());
+        Ok(order)
+    }
+
+    fn post_address(&mut self, request: models::PostAddressRequest) -> Response {
+        for (param, value) in [
+            ("name", &request.name),
+            ("street1", &request.street1),
+            ("city", &request.city),
+        ] {
+            if value.trim().is_empty() {
+                return Err(ApiError::validation(
+                    "invalid_address",
+                    format!("{} can't be empty", param),
+                    param,
+                ));
+            }
+        }
+        let request = request
+            .normalize()
+            .map_err(|err| ApiError::validation("invalid_address", err.to_string(), err.field()))?;
+
+        let id = AddressId::from(self.id("shp"));
+        let mut address = models::Address::new(
+            id.clone(),
+            request.name,
+            request.street1,
+            request.city,
+            request.country.into(),
+            request.zip,
+        );
+        address.street2 = request.street2;
+        address.province = request.province;
+        address.phone = request.phone;
+        self.addresses.push(address);
+        Ok(json!(id))
+    }
+
+    fn delete_address(&mut self, id: &str) -> Response {
+        let response = remove(&mut self.addresses, id, |a| &a.id, "address")?;
+        if self.cart.address_id.as_ref().is_some_and(|cart| cart == id) {
+            self.cart.address_id = None;
+            self.reprice_cart();
+        }
+        Ok(response)
+    }
+
+    /// Saves a card from a Stripe test token, e.g. `tok_visa` or `tok_amex`
+    fn post_card(&mut self, request: models::PostCardRequest) -> Response {
+        let (brand, last4) = match request.token.as_str() {
+            "tok_mastercard" => (models::CardBrand::Mastercard, "4444"),
+            "tok_amex" => (models::CardBrand::AmericanExpress, "8431"),
+            "tok_discover" => (models::CardBrand::Discover, "1117"),
+            token if token.starts_with("tok_") => (models::CardBrand::Visa, "4242"),
+            _ => {
+                return Err(ApiError::validation(
+                    "invalid_token",
+                    "expected a Stripe card token like `tok_visa`",
+                    "token",
+                ));
+            }
+        };
+
+        let id = CardId::from(self.id("crd"));
+        self.cards.push(models::Card::new(
+            id.clone(),
+            brand,
+            models::CardExpiration::new(2030, 12),
+            last4.to_owned(),
+        ));
+        Ok(json!(id))
+    }
+
+    fn delete_card(&mut self, id: &str) -> Response {
+        let response = remove(&mut self.cards, id, |c| &c.id, "card")?;
+        if self.cart.card_id.as_ref().is_some_and(|cart| cart == id) {
+            self.cart.card_id = None;
+        }
+        Ok(response)
+    }
+
+    fn post_subscription(&mut self, request: models::Subscription) -> Response {
+        let (product, _) = self.variant(&request.product_variant_id, "productVariantID")?;
+        if product.subscription.is_none() {
+            return Err(ApiError::validation(
+                "subscription_not_allowed",
+                format!("`{}` can't be subscribed to", product.name),
+                "productVariantID",
+            ));
+        }
+        if request.quantity <= 0 {
+            return Err(ApiError::validation(
+                "invalid_quantity",
+                "quantity must be positive",
+                "quantity",
+            ));
+        }
+        self.address(&request.address_id, "addressID")?;
+        self.card(&request.card_id, "cardID")?;
+
+        // the server assigns the ID, whatever the request had
+        let subscription = models::Subscription {
+            id: SubscriptionId::from(self.id("sub")),
+            ..request
+        };
+        self.subscriptions.push(subscription);
+        Ok(ack())
+    }
+
+    fn post_app(&mut self, request: models::PostAppRequest) -> Response {
+        if request.name.trim().is_empty() {
+            return Err(ApiError::validation(
+                "invalid_name",
+                "name can't be empty",
+                "name",
+            ));
+        }
+        if !request.redirect_uri.starts_with("http://")
+            && !request.redirect_uri.starts_with("https://")
+        {
+            return Err(ApiError::validation(
+                "invalid_redirect_uri",
+                "the redirect URI must be an http(s) URL",
+                "redirectURI",
+            ));
+        }
+
+        let id = AppId::from(self.id("cli"));
+        let secret = self.id("sec");
+        self.apps.push(models::App::new(
+            id.clone(),
+            request.name,
+            request.redirect_uri,
+            secret.clone(),
+        ));
+        Ok(json!({ "id": id, "secret": secret }))
+    }
+}
+
+fn ack() -> Value {
+    json!(models::AckStatus::Ok)
+}
+
+/// What shipping to `country` costs
+pub(super) fn shipping_cost(country: &Country) -> Money {
+    if *country == CountryCode::US {
+        Money::from_cents(0)
+    } else {
+        INTERNATIONAL_SHIPPING
+    }
+}
+
+/// The carrier and delivery time for shipping to `country`
+pub(super) fn cart_shipping(country: &Country) -> models::CartShipping {
+    let (service, timeframe) = if *country == CountryCode::US {
+        ("USPS", "3-5 days")
+    } else {
+        ("DHL", "7-14 days")
+    };
+    models::CartShipping {
+        service: Some(service.to_owned()),
+        timeframe: Some(timeframe.to_owned()),
+    }
+}
+
+fn validate_email(email: &str) -> Result<(), ApiError> {
+    match email.split_once('@') {
+        Some((user, domain)) if !user.is_empty() && domain.contains('.') => Ok(()),
+        _ => Err(ApiError::validation(
+            "invalid_email",
+            format!("`{}` is not a valid email address", email),
+            "email",
+        )),
+    }
+}
+
+fn parse<T: DeserializeOwned>(body: &[u8]) -> Result<T, ApiError> {
+    serde_json::from_slice(body).map_err(ApiError::invalid_body)
+}
+
+fn find<T: serde::Serialize, I: AsRef<str>>(
+    items: &[T],
+    id: &str,
+    item_id: impl Fn(&T) -> &I,
+    what: &str,
+) -> Response {
+    items
+        .iter()
+        .find(|item| item_id(item).as_ref() == id)
+        .map(|item| json!(item))
+        .ok_or_else(|| ApiError::not_found(format!("{} `{}` not found", what, id)))
+}
+
+fn remove<T, I: AsRef<str>>(
+    items: &mut Vec<T>,
+    id: &str,
+    item_id: impl Fn(&T) -> &I,
+    what: &str,
+) -> Response {
+    let index = items
+        .iter()
+        .position(|item| item_id(item).as_ref() == id)
+        .ok_or_else(|| ApiError::not_found(format!("{} `{}` not found", what, id)))?;
+    items.remove(index);
+    Ok(ack())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant(state: &State, product: usize) -> VariantId {
+        state.products[product].variants[0].id.clone()
+    }
+
+    fn request(value: Value) -> Vec<u8> {
+        serde_json::to_vec(&value).unwrap()
+    }
+
+    fn add_address(state: &mut State, country: &str, zip: &str) -> Value {
+        let body = request(json!({
+            "name": "Ada", "street1": "1 Main St", "city": "Springfield",
+            "province": if country == "US" { Some("IL") } else { None },
+            "country": country, "zip": zip,
+        }));
+        state.handle("post_address", None, &body).unwrap()
+    }
+
+    #[test]
+    fn test_cart_subtotal_is_the_sum_of_its_items() {
+        let mut state = State::new(None);
+        let flow = variant(&state, 0);
+        let segfault = variant(&state, 2);
+
+        let body = request(json!({ "productVariantID": flow, "quantity": 2 }));
+        state.handle("put_cart_item", None, &body).unwrap();
+        let body = request(json!({ "productVariantID": segfault, "quantity": 1 }));
+        let cart = state.handle("put_cart_item", None, &body).unwrap();
+        assert_eq!(cart["subtotal"], 6600);
+        assert_eq!(cart["amount"]["subtotal"], 6600);
+
+        let body = request(json!({ "productVariantID": flow, "quantity": 0 }));
+        let cart = state.handle("put_cart_item", None, &body).unwrap();
+        assert_eq!(cart["items"].as_array().unwrap().len(), 1);
+        assert_eq!(cart["subtotal"], 2200);
+    }
+
+    #[test]
+    fn test_put_cart_item_rules() {
+        let mut state = State::new(None);
+        let cron = variant(&state, 1);
+
+        let body = request(json!({ "productVariantID": "var_missing", "quantity": 1 }));
+        let err = state.handle("put_cart_item", None, &body).unwrap_err();
+        assert_eq!(err.status, 400);
+        assert_eq!(err.body.param.as_deref(), Some("productVariantID"));
+
+        let body = request(json!({ "productVariantID": cron, "quantity": 1 }));
+        let err = state.handle("put_cart_item", None, &body).unwrap_err();
+        assert_eq!(err.body.code, "subscription_required");
+    }
+
+    #[test]
+    fn test_convert_requires_address_and_card() {
+        let mut state = State::new(None);
+        let body = request(json!({ "productVariantID": variant(&state, 0), "quantity": 1 }));
+        state.handle("put_cart_item", None, &body).unwrap();
+
+        let err = state.handle("post_cart_convert", None, &[]).unwrap_err();
+        assert_eq!(err.body.code, "missing_address");
+
+        let address = add_address(&mut state, "DE", "10115");
+        let body = request(json!({ "addressID": address }));
+        state.handle("put_cart_address", None, &body).unwrap();
+        let err = state.handle("post_cart_convert", None, &[]).unwrap_err();
+        assert_eq!(err.body.code, "missing_card");
+
+        let card = state
+            .handle("post_card", None, &request(json!({ "token": "tok_visa" })))
+            .unwrap();
+        state
+            .handle("put_cart_card", None, &request(json!({ "cardID": card })))
+            .unwrap();
+        let order = state.handle("post_cart_convert", None, &[]).unwrap();
+        assert_eq!(order["amount"]["subtotal"], 2200);
+        assert_eq!(order["amount"]["shipping"], 1000);
+        assert_eq!(order["shipping"]["country"], "DE");
+
+        let cart = state.handle("get_cart", None, &[]).unwrap();
+        assert!(cart["items"].as_array().unwrap().is_empty());
+        assert_eq!(state.handle("get_order", None, &[]).unwrap()[0], order);
+    }
+
+    #[test]
+    fn test_addresses_are_validated_and_normalized() {
+        let mut state = State::new(None);
+        let id = add_address(&mut state, "GB", "sw1a1aa");
+        let address = state.handle("get_address_by_id", id.as_str(), &[]).unwrap();
+        assert_eq!(address["zip"], "SW1A 1AA");
+
+        let body = request(json!({
+            "name": "Ada", "street1": "1 Main St", "city": "Springfield",
+            "country": "US", "zip": "62704",
+        }));
+        let err = state.handle("post_address", None, &body).unwrap_err();
+        assert_eq!(err.body.param.as_deref(), Some("province"));
+    }
+
+    #[test]
+    fn test_missing_resources() {
+        let mut state = State::new(None);
+        for operation in ["get_card_by_id", "delete_address_by_id", "get_order_by_id"] {
+            let err = state.handle(operation, Some("nope"), &[]).unwrap_err();
+            assert_eq!(err.status, 404);
+            assert_eq!(err.body.r#type, ErrorType::NotFound);
+        }
+
+        let err = state.handle("post_card", None, b"not json").unwrap_err();
+        assert_eq!(err.status, 400);
+        assert_eq!(err.body.code, "invalid_body");
+    }
+}
@@ -0,0 +1,300 @@
+//! `terminal_client!`, a procedural macro alternative to generating the client
+//! in a build script.
+//!
+//! it runs the same `build-rs` pipeline as the `terminal` crate's build script
+//! while the invoking crate compiles, and expands to the client `impl` blocks,
+//! the sub-clients and the API trait in place. with `spec`, the client is
+//! generated from the OpenAPI document and the build fails if the API module
+//! sources disagree with it; without, it's generated from the sources. paths
+//! are relative to the invoking crate's `Cargo.toml`.
+//!
+//! ```ignore
+//! use openapi::{apis, models};
+//! pub use openapi::apis::configuration::Configuration as Config;
+//!
+//! pub struct Client { pub config: Config }
+//! pub struct RawClient<'a> { client: &'a Client }
+//! pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+//!
+//! terminal_macros::terminal_client! {
+//!     spec = "../openapi.json",
+//!     apis = "../openapi/src/apis",
+//!     models = "../openapi/src/models",
+//!     method_names = "method_names.toml",
+//!     id_types = "address: AddressId, card: CardId",
+//!     response_type_aliases = "models::DeleteAddressById200Response => models::Ack",
+//! }
+//! ```
+//!
+//! the expansion names `apis`, `models`, `Config`, `Client`, `RawClient` and
+//! `BoxFuture` as written above, so they must be in scope where the macro is
+//! invoked. every `GeneratorConfig` setting can be changed with an argument of
+//! the same name, e.g. `client_name = "PetStore"`, `excluded_files = "mod,
+//! configuration"`, `min_methods = 36`. `id_types` takes `resource: Type`
+//! pairs and `response_type_aliases` takes `from => to` pairs, both separated
+//! by commas. `strict` and `feature_gates` take `true` or `false`.
+//!
+//! proc macros can't emit warnings on stable, so functions `build-rs` has to
+//! skip, and finding fewer functions than `min_methods`, are only reported in
+//! strict mode (`strict = true` or `TERMINAL_BUILD_STRICT=1`), where they fail
+//! compilation.
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use build_rs::{
+    BuildError, GeneratorConfig, apply_method_names, check_feature_gates, check_method_count,
+    check_spec_agreement, discover_api_module_names, discover_envelope_types,
+    discover_required_models, ergonomic_params, generate_client_impl, load_method_names,
+    load_openapi_document, parse_api_functions, strict_mode, unwrap_envelopes,
+};
+use proc_macro::TokenStream;
+use quote::{ToTokens, quote};
+use syn::{Expr, ExprLit, Lit, MetaNameValue, Token, Type, parse::Parser, punctuated::Punctuated};
+
+/// generates an API client from the OpenAPI Generator code in `apis`.
+/// see the crate docs for the arguments
+#[proc_macro]
+pub fn terminal_client(input: TokenStream) -> TokenStream {
+    let args = match Punctuated::<MetaNameValue, Token![,]>::parse_terminated.parse(input) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    match expand(&args) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => {
+            let message = err.to_string();
+            quote! { compile_error!(#message); }.into()
+        }
+    }
+}
+
+/// the macro's arguments
+#[derive(Default)]
+struct Args {
+    spec: Option<PathBuf>,
+    apis: Option<PathBuf>,
+    models: Option<PathBuf>,
+    method_names: Option<PathBuf>,
+    strict: bool,
+}
+
+fn expand(
+    input: &Punctuated<MetaNameValue, Token![,]>,
+) -> Result<proc_macro2::TokenStream, BuildError> {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR")?);
+    let crate_name = env::var("CARGO_CRATE_NAME")?;
+    let mut config = GeneratorConfig::new(&crate_name, &default_api_name(&crate_name));
+    let mut args = Args::default();
+
+    for arg in input {
+        let key = arg
+            .path
+            .get_ident()
+            .map(ToString::to_string)
+            .ok_or("expected an argument like `apis = \"...\"`")?;
+        let value = arg_value(&arg.value).ok_or_else(|| {
+            format!(
+                "the value of `{}` must be a string, integer or bool literal",
+                key
+            )
+        })?;
+
+        match key.as_str() {
+            "spec" => args.spec = Some(manifest_dir.join(value)),
+            "apis" => args.apis = Some(manifest_dir.join(value)),
+            "models" => args.models = Some(manifest_dir.join(value)),
+            "method_names" => args.method_names = Some(manifest_dir.join(value)),
+            "strict" => args.strict = bool_value(&key, &arg.value)?,
+            "crate_name" => config.crate_name = value,
+            "api_name" => config.api_name = value,
+            "client_name" => config.client_name = value,
+            "config_type" => config.config_type = value,
+            "config_param" => config.config_param = value,
+            "error_type" => config.error_type = value,
+            "module_path" => config.module_path = value,
+            "module_suffix" => config.module_suffix = value,
+            "excluded_files" => {
+                config.excluded_files = value
+                    .split(',')
+                    .map(|file| file.trim().to_string())
+                    .filter(|file| !file.is_empty())
+                    .collect()
+            }
+            "id_types" => config.id_types = pairs(&value, ":")?,
+            "response_type_aliases" => {
+                config.response_type_aliases = pairs(&value, "=>")?
+                    .into_iter()
+                    .map(|(from, to)| Ok((type_string(&from)?, type_string(&to)?)))
+                    .collect::<Result<_, BuildError>>()?
+            }
+            "feature_gates" => config.feature_gates = bool_value(&key, &arg.value)?,
+            "min_methods" => {
+                config.min_methods = Some(
+                    value
+                        .parse()
+                        .map_err(|_| "the value of `min_methods` must be a number")?,
+                )
+            }
+            _ => return Err(format!("unknown argument `{}`", key).into()),
+        }
+    }
+
+    let apis_folder = args.apis.ok_or("missing the `apis` argument")?;
+    let api_modules = discover_api_module_names(&apis_folder, &config)?;
+    let (generated, skipped) = parse_api_functions(&apis_folder, &api_modules, &config)?;
+    let strict = args.strict || strict_mode();
+    if !skipped.is_empty() && strict {
+        let skipped: Vec<_> = skipped.iter().map(ToString::to_string).collect();
+        return Err(format!(
+            "API functions would be dropped from the client:\n{}",
+            skipped.join("\n")
+        )
+        .into());
+    }
+    if strict {
+        check_method_count(generated.len(), &config, strict)?;
+    }
+
+    // the expansion must be recompiled when a source it was generated from changes
+    let mut sources: Vec<PathBuf> = api_modules
+        .iter()
+        .map(|module| apis_folder.join(format!("{}.rs", module)))
+        .collect();
+
+    let mut functions = match &args.spec {
+        Some(spec_path) => {
+            let functions = load_openapi_document(spec_path, &config)?;
+            check_spec_agreement(&functions, &generated)?;
+            sources.push(spec_path.clone());
+            functions
+        }
+        None => generated,
+    };
+
+    if let Some(models_folder) = &args.models {
+        if config.feature_gates {
+            check_feature_gates(&apis_folder, models_folder, &functions)?;
+        }
+        unwrap_envelopes(&mut functions, &discover_envelope_types(models_folder)?);
+        ergonomic_params(&mut functions, &discover_required_models(models_folder)?);
+        sources.extend(rust_files(models_folder)?);
+    }
+    if let Some(method_names_path) = &args.method_names {
+        apply_method_names(&mut functions, &load_method_names(method_names_path)?)?;
+        sources.push(method_names_path.clone());
+    }
+
+    let client: proc_macro2::TokenStream = generate_client_impl(&functions, &config)?.parse()?;
+    let tracked = sources
+        .iter()
+        .map(|path| path.to_string_lossy().into_owned());
+
+    Ok(quote! {
+        #(const _: &[u8] = include_bytes!(#tracked);)*
+
+        #client
+    })
+}
+
+/// the value of a `key = true` argument, rejecting anything but a bool literal
+fn bool_value(key: &str, value: &Expr) -> Result<bool, BuildError> {
+    match value {
+        Expr::Lit(ExprLit {
+            lit: Lit::Bool(b), ..
+        }) => Ok(b.value),
+        _ => Err(format!("the value of `{}` must be `true` or `false`", key).into()),
+    }
+}
+
+/// the `left <separator> right` pairs of a comma-separated argument
+fn pairs(value: &str, separator: &str) -> Result<Vec<(String, String)>, BuildError> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            pair.split_once(separator)
+                .map(|(left, right)| (left.trim().to_string(), right.trim().to_string()))
+                .ok_or_else(|| format!("expected `{}` in `{}`", separator, pair).into())
+        })
+        .collect()
+}
+
+/// a type as the generator writes it, e.g. `models :: Ack` for `models::Ack`
+fn type_string(ty: &str) -> Result<String, BuildError> {
+    Ok(syn::parse_str::<Type>(ty)?.to_token_stream().to_string())
+}
+
+/// the value of a `key = "value"`, `key = 1` or `key = true` argument
+fn arg_value(value: &Expr) -> Option<String> {
+    match value {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(s), ..
+        }) => Some(s.value()),
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(i), ..
+        }) => Some(i.base10_digits().to_string()),
+        Expr::Lit(ExprLit {
+            lit: Lit::Bool(b), ..
+        }) => Some(b.value.to_string()),
+        _ => None,
+    }
+}
+
+/// the API name used when none is given, e.g. `Terminal` for the `terminal` crate
+fn default_api_name(crate_name: &str) -> String {
+    crate_name
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// the `.rs` files in a folder, sorted
+fn rust_files(folder: &Path) -> Result<Vec<PathBuf>, BuildError> {
+    let mut files = std::fs::read_dir(folder)?
+        .map(|entry| Ok(entry?.path()))
+        .collect::<Result<Vec<_>, std::io::Error>>()?;
+    files.retain(|path| path.extension().is_some_and(|ext| ext == "rs"));
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_bool_value() {
+        assert!(bool_value("strict", &parse_quote!(true)).unwrap());
+        let err = bool_value("strict", &parse_quote!("yes")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the value of `strict` must be `true` or `false`"
+        );
+    }
+
+    #[test]
+    fn test_pairs() -> Result<(), BuildError> {
+        assert_eq!(
+            pairs("address: AddressId, card: CardId,", ":")?,
+            vec![
+                ("address".to_string(), "AddressId".to_string()),
+                ("card".to_string(), "CardId".to_string()),
+            ]
+        );
+        assert!(pairs("address AddressId", ":").is_err());
+        assert_eq!(type_string("models::Ack")?, "models :: Ack");
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use openapi::apis;
+use openapi::apis::configuration::Configuration as Config;
+
+mod models {
+    pub use openapi::models::DeleteAddressById200Response as Ack;
+    pub use openapi::models::*;
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Client {
+    pub config: Config,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RawClient<'a> {
+    client: &'a Client,
+}
+
+impl Client {
+    pub fn raw(&self) -> RawClient<'_> {
+        RawClient { client: self }
+    }
+}
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+terminal_macros::terminal_client! {
+    spec = "../openapi.json",
+    apis = "../openapi/src/apis",
+    models = "../openapi/src/models",
+    method_names = "../method_names.toml",
+    crate_name = "client",
+    api_name = "Terminal",
+    id_types = "address: AddressId, card: CardId, product: ProductId",
+    response_type_aliases = "models::DeleteAddressById200Response => models::Ack",
+    strict = true,
+}
+
+/// checks a future's output type without running it
+fn returns<T>(_: impl Future<Output = T>) {}
+
+#[test]
+fn test_generated_client() {
+    let client = Client::new(Config::default());
+
+    // flat, renamed, sub-client and raw methods all exist with the expected types
+    returns::<Result<models::Cart, _>>(client.get_cart());
+    returns::<Result<Vec<models::Address>, _>>(client.list_addresses());
+    returns::<Result<models::Cart, _>>(client.cart().get());
+    returns::<Result<models::GetCart200Response, _>>(client.raw().get_cart());
+
+    // by-id methods take the resource's ID newtype
+    returns::<Result<models::Product, _>>(
+        client.get_product_by_id(models::ProductId::from("prd_XXXXXXXXXXXXXXXXXXXXXXXXX")),
+    );
+    // and acknowledgements are aliased, as in the `terminal` crate
+    returns::<Result<models::Ack, _>>(
+        client.delete_address_by_id(models::AddressId::from("shp_XXXXXXXXXXXXXXXXXXXXXXXXX")),
+    );
+
+    let api: &dyn TerminalApi = &client;
+    returns::<Result<models::Cart, _>>(api.get_cart());
+}